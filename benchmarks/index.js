@@ -5,7 +5,8 @@ const benches = require('./pkg');
 
 let suites = {
 	parse: new Suite('parse'),
-	serialize: new Suite('serialize')
+	serialize: new Suite('serialize'),
+	coordinates: new Suite('coordinates')
 };
 
 for (let input of ['canada', 'citm_catalog', 'twitter']) {
@@ -23,6 +24,15 @@ for (let input of ['canada', 'citm_catalog', 'twitter']) {
 	}
 }
 
+{
+	const parsed = benches.parse_canada_with_serde_json(require('./canada.json'));
+
+	for (const lib of ['array_push', 'picoserde_wasm_bindgen']) {
+		const serialize = benches[`serialize_canada_coordinates_with_${lib}`];
+		suites.coordinates.add(`canada coordinates x ${lib}`, () => serialize(parsed));
+	}
+}
+
 function runSuite(suite) {
 	console.log('='.repeat(suite.name.length));
 	console.log(suite.name);
@@ -39,4 +49,5 @@ if (process.argv.length > 2) {
 } else {
 	runSuite(suites.parse);
 	runSuite(suites.serialize);
+	runSuite(suites.coordinates);
 }
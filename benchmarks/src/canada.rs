@@ -1,10 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap as Map;
-use picoserde_wasm_bindgen::{DeJs, SerJs};
 
 pub type Canada = FeatureCollection;
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct FeatureCollection {
     #[serde(rename = "type")]
@@ -12,7 +11,7 @@ pub struct FeatureCollection {
     pub features: Vec<Feature>,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Feature {
     #[serde(rename = "type")]
@@ -21,7 +20,7 @@ pub struct Feature {
     pub geometry: Geometry,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Geometry {
     #[serde(rename = "type")]
@@ -32,7 +31,7 @@ pub struct Geometry {
 pub type Latitude = f32;
 pub type Longitude = f32;
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub enum ObjType {
     FeatureCollection,
@@ -1,9 +1,8 @@
 use crate::prim_str::PrimStr;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap as Map;
-use picoserde_wasm_bindgen::*;
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct CitmCatalog {
     pub area_names: Map<IdStr, String>,
@@ -22,7 +21,7 @@ pub struct CitmCatalog {
 pub type Id = u32;
 pub type IdStr = PrimStr<u32>;
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct Event {
     pub description: (),
@@ -35,7 +34,7 @@ pub struct Event {
     pub topic_ids: Vec<Id>,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct Performance {
     pub event_id: Id,
@@ -49,7 +48,7 @@ pub struct Performance {
     pub venue_code: String,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct Price {
     pub amount: u32,
@@ -57,14 +56,14 @@ pub struct Price {
     pub seat_category_id: Id,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct SeatCategory {
     pub areas: Vec<Area>,
     pub seat_category_id: Id,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct Area {
     pub area_id: Id,
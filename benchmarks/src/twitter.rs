@@ -1,9 +1,8 @@
 use crate::color::Color;
 use crate::prim_str::PrimStr;
 use serde::{Deserialize, Serialize};
-use picoserde_wasm_bindgen::*;
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Twitter {
     pub statuses: Vec<Status>,
@@ -16,7 +15,7 @@ pub type ShortId = u32;
 pub type LongIdStr = PrimStr<LongId>;
 pub type ShortIdStr = PrimStr<ShortId>;
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Status {
     pub metadata: Metadata,
@@ -46,14 +45,14 @@ pub struct Status {
     pub lang: LanguageCode,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Metadata {
     pub result_type: ResultType,
     pub iso_language_code: LanguageCode,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct User {
     pub id: ShortId,
@@ -98,20 +97,20 @@ pub struct User {
     pub notifications: bool,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct UserEntities {
     pub url: Option<UserUrl>,
     pub description: UserEntitiesDescription,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct UserUrl {
     pub urls: Vec<Url>,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Url {
     pub url: String,
@@ -120,13 +119,13 @@ pub struct Url {
     pub indices: Indices,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct UserEntitiesDescription {
     pub urls: Vec<Url>,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct StatusEntities {
     pub hashtags: Vec<Hashtag>,
@@ -136,14 +135,14 @@ pub struct StatusEntities {
     pub media: Option<Vec<Media>>,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Hashtag {
     pub text: String,
     pub indices: Indices,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct UserMention {
     pub screen_name: String,
@@ -153,7 +152,7 @@ pub struct UserMention {
     pub indices: Indices,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Media {
     pub id: LongId,
@@ -172,7 +171,7 @@ pub struct Media {
 }
 
 #[derive(Serialize, Deserialize)]
-#[serde(deny_unknown_fields, DeJs, SerJs)]
+#[serde(deny_unknown_fields)]
 pub struct Sizes {
     pub medium: Size,
     pub small: Size,
@@ -181,7 +180,7 @@ pub struct Sizes {
 }
 
 #[derive(Serialize, Deserialize)]
-#[serde(deny_unknown_fields, DeJs, SerJs)]
+#[serde(deny_unknown_fields)]
 pub struct Size {
     pub w: u16,
     pub h: u16,
@@ -190,7 +189,7 @@ pub struct Size {
 
 pub type Indices = (u8, u8);
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct SearchMetadata {
     pub completed_in: f32,
@@ -204,7 +203,7 @@ pub struct SearchMetadata {
     pub since_id_str: LongIdStr,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub enum Resize {
     #[serde(rename = "fit")]
@@ -213,7 +212,7 @@ pub enum Resize {
     Crop,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub enum LanguageCode {
     #[serde(rename = "zh-cn")]
@@ -230,7 +229,7 @@ pub enum LanguageCode {
     Zh,
 }
 
-#[derive(Serialize, Deserialize, DeJs, SerJs)]
+#[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub enum ResultType {
     #[serde(rename = "recent")]
@@ -34,13 +34,166 @@ pub struct Field {
 #[derive(Debug)]
 pub struct Type {
     pub is_option: bool,
+    /// `Option<Option<T>>`, telling an absent value apart from `null`
+    pub is_double_option: bool,
     pub path: String,
 }
 
+impl Type {
+    /// Whether the identifier `name`, like a type parameter, appears in the type
+    pub fn mentions(&self, name: &str) -> bool {
+        mentions(&self.path, name)
+    }
+
+    /// Whether the identifier `name` appears in the parameter of an `Rc` or
+    /// `Arc` of the type
+    pub fn mentions_in_shared(&self, name: &str) -> bool {
+        self.path.char_indices().any(|(start, _)| {
+            let rest = &self.path[start..];
+            let param_start = if rest.starts_with("Rc<") {
+                start + 3
+            } else if rest.starts_with("Arc<") {
+                start + 4
+            } else {
+                return false;
+            };
+            if let Some(c) = self.path[..start].chars().next_back() {
+                if c.is_alphanumeric() || c == '_' {
+                    return false;
+                }
+            }
+            // the parameter, up to the matching `>`
+            let mut depth = 1;
+            let mut param_end = self.path.len();
+            for (i, c) in self.path[param_start..].char_indices() {
+                match c {
+                    '<' => depth += 1,
+                    '>' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            param_end = param_start + i;
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            mentions(&self.path[param_start..param_end], name)
+        })
+    }
+
+    /// Whether the type is `PhantomData`, which holds no value of its parameter
+    pub fn is_phantom_data(&self) -> bool {
+        let path = self.path.split('<').next().unwrap_or_default();
+        path.rsplit("::").next() == Some("PhantomData")
+    }
+}
+
+fn mentions(path: &str, name: &str) -> bool {
+    path.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .any(|part| part == name)
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GenericParamKind {
+    Lifetime,
+    Type,
+    Const,
+}
+
+#[derive(Debug)]
+pub struct GenericParam {
+    pub kind: GenericParamKind,
+    /// `'a`, `T` or `N`
+    pub name: String,
+    /// the declaration without its default value, like `T: Clone`
+    pub declaration: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Generics {
+    pub params: Vec<GenericParam>,
+    /// predicates of the `where` clause
+    pub where_predicates: Vec<String>,
+}
+
+impl Generics {
+    /// `<'a, T: Clone, const N: usize>`, to declare the parameters on an impl
+    pub fn impl_generics(&self) -> String {
+        if self.params.is_empty() {
+            return String::new();
+        }
+        let declarations: Vec<&str> = self.params.iter().map(|p| p.declaration.as_str()).collect();
+        format!("<{}>", declarations.join(", "))
+    }
+
+    /// `<'a, T, N>`, to name the type in an impl
+    pub fn ty_generics(&self) -> String {
+        if self.params.is_empty() {
+            return String::new();
+        }
+        let names: Vec<&str> = self.params.iter().map(|p| p.name.as_str()).collect();
+        format!("<{}>", names.join(", "))
+    }
+
+    /// The `where` clause of an impl of `bound`, with `T: bound` inferred for
+    /// each type parameter used in the types of `fields`, or `P: bound` for
+    /// the proxies `P` using them. `PhantomData` fields don't need any bound.
+    /// With `static_in_shared`, the parameters used in an `Rc` or `Arc` are
+    /// also bound by `'static`.
+    pub fn where_clause<'a>(
+        &self,
+        bound: &str,
+        static_in_shared: bool,
+        fields: impl IntoIterator<Item=&'a Field>,
+    ) -> String {
+        let mut field_types = Vec::new();
+        let mut proxies = Vec::new();
+        for field in fields {
+            match crate::shared::attrs_proxy(&field.attributes) {
+                Some(proxy) => proxies.push(proxy),
+                None if field.ty.is_phantom_data() => {}
+                None => field_types.push(&field.ty),
+            }
+        }
+        let type_params: Vec<&str> = self
+            .params
+            .iter()
+            .filter(|param| param.kind == GenericParamKind::Type)
+            .map(|param| param.name.as_str())
+            .collect();
+
+        let mut predicates = self.where_predicates.clone();
+        for param in &type_params {
+            if static_in_shared && field_types.iter().any(|ty| ty.mentions_in_shared(param)) {
+                predicates.push(format!("{}: {} + 'static", param, bound));
+            } else if field_types.iter().any(|ty| ty.mentions(param)) {
+                predicates.push(format!("{}: {}", param, bound));
+            }
+        }
+        for proxy in proxies {
+            let predicate = format!("{}: {}", proxy, bound);
+            if type_params.iter().any(|param| mentions(&proxy, param))
+                && !predicates.contains(&predicate)
+            {
+                predicates.push(predicate);
+            }
+        }
+        if predicates.is_empty() {
+            String::new()
+        } else {
+            format!("where {}", predicates.join(", "))
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Struct {
     pub name: String,
+    pub generics: Generics,
     pub named: bool,
+    /// `struct Marker;`, as opposed to `struct Empty();` or `struct Empty {}`
+    pub unit: bool,
     pub fields: Vec<Field>,
     pub attributes: Vec<Attribute>,
 }
@@ -55,6 +208,7 @@ pub struct EnumVariant {
 #[derive(Debug)]
 pub struct Enum {
     pub name: String,
+    pub generics: Generics,
     pub variants: Vec<EnumVariant>,
     pub attributes: Vec<Attribute>,
 }
@@ -75,6 +229,14 @@ impl Data {
         }
     }
 
+    pub fn generics(&self) -> &Generics {
+        match self {
+            Data::Struct(Struct { generics, .. }) => generics,
+            Data::Enum(Enum { generics, .. }) => generics,
+            _ => unimplemented!(),
+        }
+    }
+
     pub fn attributes(&self) -> &[Attribute] {
         match self {
             Data::Struct(Struct { attributes, .. }) => &attributes[..],
@@ -139,6 +301,21 @@ pub fn next_literal(source: &mut Peekable<impl Iterator<Item=TokenTree>>) -> Opt
     }
 }
 
+pub fn next_exact_ident(
+    source: &mut Peekable<impl Iterator<Item=TokenTree>>,
+    pattern: &str,
+) -> Option<String> {
+    if let Some(TokenTree::Ident(ident)) = source.peek() {
+        let ident = ident.to_string();
+        if ident == pattern {
+            source.next();
+            return Some(ident);
+        }
+    }
+
+    None
+}
+
 pub fn next_eof<T: Iterator>(source: &mut Peekable<T>) -> Option<()> {
     if source.peek().is_none() {
         Some(())
@@ -175,6 +352,44 @@ pub fn debug_current_token(source: &mut Peekable<impl Iterator<Item=TokenTree>>)
 }
 
 fn next_type<T: Iterator<Item=TokenTree>>(mut source: &mut Peekable<T>) -> Option<Type> {
+    // tuples, including the unit type `()`, and arrays
+    if let Some(group) = next_group(source) {
+        return Some(Type {
+            path: group.to_string(),
+            is_option: false,
+            is_double_option: false,
+        });
+    }
+
+    // references, like `&'a str` or `&mut T`
+    if next_exact_punct(source, "&").is_some() {
+        let mut path = "&".to_string();
+        if next_exact_punct(source, "'").is_some() {
+            let lifetime = next_ident(source).expect("Expecting lifetime name");
+            path.push_str(&format!("'{} ", lifetime));
+        }
+        if next_exact_ident(source, "mut").is_some() {
+            path.push_str("mut ");
+        }
+        let referent = next_type(source).expect("Expecting referenced type");
+        path.push_str(&referent.path);
+        return Some(Type {
+            path,
+            is_option: false,
+            is_double_option: false,
+        });
+    }
+
+    // lifetime arguments, like in `Cow<'static, str>`
+    if next_exact_punct(source, "'").is_some() {
+        let lifetime = next_ident(source).expect("Expecting lifetime name");
+        return Some(Type {
+            path: format!("'{}", lifetime),
+            is_option: false,
+            is_double_option: false,
+        });
+    }
+
     let mut ty = next_ident(&mut source)?;
 
     while let Some(_) = next_exact_punct(&mut source, ":") {
@@ -198,21 +413,154 @@ fn next_type<T: Iterator<Item=TokenTree>>(mut source: &mut Peekable<T>) -> Optio
 
         if ty == "Option" {
             Some(Type {
-                path: generic_type.path,
+                path: if generic_type.is_option {
+                    format!("Option<{}>", generic_type.path)
+                } else {
+                    generic_type.path
+                },
                 is_option: true,
+                is_double_option: generic_type.is_option,
             })
         } else {
             Some(Type {
                 path: format!("{}<{}>", ty, generic_type.path),
                 is_option: false,
+                is_double_option: false,
             })
         }
     } else {
         Some(Type {
             path: ty,
             is_option: false,
+            is_double_option: false,
+        })
+    }
+}
+
+fn tokens_to_string(tokens: &[TokenTree]) -> String {
+    tokens.iter().cloned().collect::<TokenStream>().to_string()
+}
+
+/// Splits tokens on the commas outside of `<>`, like the ones between generic
+/// parameters or `where` predicates.
+fn split_top_level_commas(tokens: Vec<TokenTree>) -> Vec<Vec<TokenTree>> {
+    let mut parts = vec![Vec::new()];
+    let mut depth = 0;
+    let mut after_dash = false;
+    for token in tokens {
+        if let TokenTree::Punct(punct) = &token {
+            match punct.as_char() {
+                '<' => depth += 1,
+                // `->` of `Fn() -> T` bounds isn't a closing bracket
+                '>' if !after_dash => depth -= 1,
+                ',' if depth == 0 => {
+                    parts.push(Vec::new());
+                    continue;
+                }
+                _ => {}
+            }
+            after_dash = punct.as_char() == '-';
+        } else {
+            after_dash = false;
+        }
+        parts.last_mut().unwrap().push(token);
+    }
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Position of the `=` before the default value of a generic parameter,
+/// skipping the ones inside `<>` like in `I: Iterator<Item = u8>`.
+fn default_position(tokens: &[TokenTree]) -> Option<usize> {
+    let mut depth = 0;
+    let mut after_dash = false;
+    for (i, token) in tokens.iter().enumerate() {
+        if let TokenTree::Punct(punct) = token {
+            match punct.as_char() {
+                '<' => depth += 1,
+                // `->` of `Fn() -> T` bounds isn't a closing bracket
+                '>' if !after_dash => depth -= 1,
+                '=' if depth == 0 => return Some(i),
+                _ => {}
+            }
+            after_dash = punct.as_char() == '-';
+        } else {
+            after_dash = false;
+        }
+    }
+    None
+}
+
+fn next_generic_params(source: &mut Peekable<impl Iterator<Item=TokenTree>>) -> Vec<GenericParam> {
+    if next_exact_punct(source, "<").is_none() {
+        return Vec::new();
+    }
+
+    let mut tokens = Vec::new();
+    let mut depth = 0;
+    let mut after_dash = false;
+    loop {
+        let token = source.next().expect("Expecting closing generic bracket");
+        if let TokenTree::Punct(punct) = &token {
+            match punct.as_char() {
+                '<' => depth += 1,
+                '>' if !after_dash && depth == 0 => break,
+                '>' if !after_dash => depth -= 1,
+                _ => {}
+            }
+            after_dash = punct.as_char() == '-';
+        } else {
+            after_dash = false;
+        }
+        tokens.push(token);
+    }
+
+    split_top_level_commas(tokens)
+        .into_iter()
+        .map(|tokens| {
+            // drop the default value, which can't be repeated on impls
+            let declaration_len = default_position(&tokens).unwrap_or(tokens.len());
+            let declaration = tokens_to_string(&tokens[..declaration_len]);
+            let (kind, name) = match &tokens[..] {
+                [TokenTree::Punct(_), TokenTree::Ident(name), ..] => {
+                    (GenericParamKind::Lifetime, format!("'{}", name))
+                }
+                [TokenTree::Ident(keyword), TokenTree::Ident(name), ..] if keyword.to_string() == "const" => {
+                    (GenericParamKind::Const, name.to_string())
+                }
+                [TokenTree::Ident(name), ..] => (GenericParamKind::Type, name.to_string()),
+                _ => panic!("Unexpected generic parameter {}", declaration),
+            };
+            GenericParam {
+                kind,
+                name,
+                declaration,
+            }
         })
+        .collect()
+}
+
+/// Predicates of a `where` clause, up to the body of a struct or the `;` of a
+/// tuple struct.
+fn next_where_predicates(source: &mut Peekable<impl Iterator<Item=TokenTree>>) -> Vec<String> {
+    if next_exact_ident(source, "where").is_none() {
+        return Vec::new();
     }
+
+    let mut tokens = Vec::new();
+    loop {
+        match source.peek() {
+            None => break,
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => break,
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ';' => break,
+            _ => tokens.push(source.next().unwrap()),
+        }
+    }
+
+    split_top_level_commas(tokens)
+        .iter()
+        .map(|tokens| tokens_to_string(tokens))
+        .collect()
 }
 
 fn next_attribute<T: Iterator<Item=TokenTree>>(
@@ -318,15 +666,22 @@ fn next_fields(
 
 fn next_struct(mut source: &mut Peekable<impl Iterator<Item=TokenTree>>) -> Struct {
     let struct_name = next_ident(&mut source).expect("Unnamed structs are not supported");
+    let mut generics = Generics {
+        params: next_generic_params(source),
+        where_predicates: next_where_predicates(source),
+    };
 
     let group = next_group(&mut source);
     // unit struct
     if group.is_none() {
+        next_exact_punct(source, ";").expect("Expected ; on the end of unit struct");
         return Struct {
             name: struct_name,
+            generics,
             fields: Vec::new(),
             attributes: Vec::new(),
             named: false,
+            unit: true,
         };
     };
     let group = group.unwrap();
@@ -342,12 +697,15 @@ fn next_struct(mut source: &mut Peekable<impl Iterator<Item=TokenTree>>) -> Stru
     let fields = next_fields(&mut body, named);
 
     if named == false {
+        generics.where_predicates = next_where_predicates(source);
         next_exact_punct(&mut source, ";").expect("Expected ; on the end of tuple struct");
     }
 
     Struct {
         name: struct_name,
+        generics,
         named,
+        unit: false,
         fields,
         attributes: Vec::new(),
     }
@@ -355,12 +713,17 @@ fn next_struct(mut source: &mut Peekable<impl Iterator<Item=TokenTree>>) -> Stru
 
 fn next_enum(mut source: &mut Peekable<impl Iterator<Item=TokenTree>>) -> Enum {
     let enum_name = next_ident(&mut source).expect("Unnamed enums are not supported");
+    let generics = Generics {
+        params: next_generic_params(source),
+        where_predicates: next_where_predicates(source),
+    };
 
     let group = next_group(&mut source);
     // unit enum
     if group.is_none() {
         return Enum {
             name: enum_name,
+            generics,
             variants: Vec::new(),
             attributes: Vec::new(),
         };
@@ -408,6 +771,7 @@ fn next_enum(mut source: &mut Peekable<impl Iterator<Item=TokenTree>>) -> Enum {
 
     Enum {
         name: enum_name,
+        generics,
         variants,
         attributes: Vec::new(),
     }
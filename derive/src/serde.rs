@@ -1,23 +1,50 @@
 use proc_macro::TokenStream;
 
-use crate::parse::{Enum, Field, Struct};
+use crate::parse::{Enum, Field, Generics, Struct};
 use crate::shared;
 
-pub fn derive_ser_js_proxy(proxy_type: &str, type_: &str) -> TokenStream {
+/// `impl<..> Trait for Type<..> where ..`, with bounds inferred from `fields`
+fn impl_header<'a>(
+    trait_: &str,
+    type_: &str,
+    generics: &Generics,
+    fields: impl IntoIterator<Item=&'a Field>,
+) -> String {
+    // `DeJs for Rc<T>` and `Arc<T>` need `T: 'static` to share pointers by type
+    let static_in_shared = trait_ == "DeJs";
     format!(
-        "impl SerJs for {} {{
+        "impl{} {} for {}{} {}",
+        generics.impl_generics(),
+        trait_,
+        type_,
+        generics.ty_generics(),
+        generics.where_clause(trait_, static_in_shared, fields)
+    )
+}
+
+pub fn derive_ser_js_proxy(proxy_type: &str, type_: &str, generics: &Generics) -> TokenStream {
+    format!(
+        "{} {{
             fn ser_js(&self) -> JsValue {{
                 let proxy: {} = self.into();
                 proxy.ser_js()
             }}
         }}",
-        type_,
+        impl_header("SerJs", type_, generics, &[]),
         proxy_type
     )
         .parse()
         .unwrap()
 }
 
+/// Serializes a reference to the value of a field, through its proxy if any
+fn field_serializer(field: &Field, value: &str) -> String {
+    match shared::attrs_proxy(&field.attributes) {
+        Some(proxy) => format!("SerJs::ser_js(&<{}>::from({}))", proxy, value),
+        None => format!("SerJs::ser_js({})", value),
+    }
+}
+
 pub fn derive_ser_js_struct(struct_: &Struct) -> TokenStream {
     let mut s = String::new();
 
@@ -26,42 +53,123 @@ pub fn derive_ser_js_struct(struct_: &Struct) -> TokenStream {
         let js_fieldname =
             shared::attrs_rename(&field.attributes).unwrap_or_else(|| struct_fieldname.clone());
 
-        if field.ty.is_option {
+        let proxy = shared::attrs_proxy(&field.attributes);
+        let ser = |value: &str| field_serializer(field, value);
+
+        if field.ty.is_double_option {
+            assert!(
+                proxy.is_none(),
+                "proxy is not supported on Option<Option<T>> fields"
+            );
             l!(
                 s,
-                "if let Some(t) = &self.{} {{ object.set(SerJs::label(self, \"{}\"), t.ser_js()); }};",
+                "match &self.{} {{
+                    Some(Some(t)) => object.set(SerJs::label(self, \"{}\"), {}),
+                    Some(None) => object.set(SerJs::label(self, \"{}\"), JsValue::NULL),
+                    None => {{}}
+                }};",
                 struct_fieldname,
+                js_fieldname,
+                ser("t"),
                 js_fieldname
             );
+        } else if field.ty.is_option {
+            let none_as = shared::attrs_none_as(&field.attributes)
+                .or_else(|| shared::attrs_none_as(&struct_.attributes));
+            match none_as.as_deref() {
+                Some("null") | Some("undefined") => {
+                    l!(
+                        s,
+                        "object.set(SerJs::label(self, \"{}\"), match &self.{} {{ Some(t) => {}, None => JsValue::{} }});",
+                        js_fieldname,
+                        struct_fieldname,
+                        ser("t"),
+                        none_as.unwrap().to_uppercase()
+                    );
+                }
+                _ => {
+                    l!(
+                        s,
+                        "if let Some(t) = &self.{} {{ object.set(SerJs::label(self, \"{}\"), {}); }};",
+                        struct_fieldname,
+                        js_fieldname,
+                        ser("t")
+                    );
+                }
+            }
         } else {
             l!(
                 s,
-                "object.set(SerJs::label(self, \"{}\"), self.{}.ser_js());",
+                "object.set(SerJs::label(self, \"{}\"), {});",
                 js_fieldname,
-                struct_fieldname
+                ser(&format!("&self.{}", struct_fieldname))
             );
         }
     }
+    // builds the object from the prototype of a JS class instead of a plain object
+    let ser_object = match shared::attrs_js_class(&struct_.attributes) {
+        Some(js_class) => format!(
+            "fn ser_object(&self) -> picoserde_wasm_bindgen::MyCustomJsObject {{
+            picoserde_wasm_bindgen::MyCustomJsObject::with_js_class(\"{}\")
+        }}",
+            js_class
+        ),
+        None => String::new(),
+    };
+    let body = format!(
+        "let object = self.ser_object();
+            {}
+            object.into()",
+        s
+    );
+    let body = match shared::attrs_freeze(&struct_.attributes) {
+        Some(deep) => format!(
+            "picoserde_wasm_bindgen::internal::ser_frozen({}, || {{ {} }})",
+            deep, body
+        ),
+        None => body,
+    };
     format!("const _: () = {{
-    impl SerJs for {} {{
+    {} {{
+        {}
         fn ser_js(&self) -> JsValue {{
-            let object = self.ser_object();
             {}
-            object.into()
         }}
     }}
 }};",
-        struct_.name,
-        s
+        impl_header("SerJs", &struct_.name, &struct_.generics, &struct_.fields),
+        ser_object,
+        body
     )
         .parse()
         .unwrap()
 }
 
+/// The function deserializing the value of a field, through its proxy if any
+fn field_deserializer(field: &Field) -> String {
+    let proxy = shared::attrs_proxy(&field.attributes);
+    if field.ty.is_double_option {
+        "picoserde_wasm_bindgen::internal::de_double_option".to_string()
+    } else if let Some(proxy) = proxy {
+        let helper = if field.ty.is_option {
+            "de_option_proxy"
+        } else {
+            "de_proxy"
+        };
+        format!(
+            "picoserde_wasm_bindgen::internal::{}::<{}, _>",
+            helper, proxy
+        )
+    } else {
+        "DeJs::de_js".to_string()
+    }
+}
+
 pub fn derive_de_js_named(name: &str, defaults: bool, fields: &[Field]) -> TokenStream {
     let mut local_vars = Vec::new();
     let mut struct_field_names = Vec::new();
     let mut js_field_names = Vec::new();
+    let mut deserializers = Vec::new();
     let mut unwraps = Vec::new();
 
     let container_attr_default = defaults;
@@ -75,10 +183,7 @@ pub fn derive_de_js_named(name: &str, defaults: bool, fields: &[Field]) -> Token
             shared::attrs_rename(&field.attributes).unwrap_or_else(|| struct_fieldname.clone());
 
         if field.ty.is_option {
-            unwraps.push(format!(
-                "{{ if let Some(t) = {} {{ t }} else {{ None }} }}",
-                localvar
-            ));
+            unwraps.push(format!("{}.unwrap_or(None)", localvar));
         } else if container_attr_default || field_attr_default {
             unwraps.push(format!(
                 "{{ if let Some(t) = {} {{ t }} else {{ Default::default() }} }}",
@@ -92,6 +197,8 @@ pub fn derive_de_js_named(name: &str, defaults: bool, fields: &[Field]) -> Token
             ));
         }
 
+        deserializers.push(field_deserializer(field));
+
         struct_field_names.push(struct_fieldname);
         js_field_names.push(js_fieldname);
         local_vars.push(localvar);
@@ -101,11 +208,17 @@ pub fn derive_de_js_named(name: &str, defaults: bool, fields: &[Field]) -> Token
         l!(r, "let mut {} = None;", local_var);
     }
 
-    for (js_field_name, local_var) in js_field_names.iter().zip(local_vars.iter()) {
+    for ((js_field_name, local_var), deserializer) in js_field_names
+        .iter()
+        .zip(local_vars.iter())
+        .zip(deserializers.iter())
+    {
         l!(
             r,
-            "{} = Some(DeJs::de_js(picoserde_wasm_bindgen::internal::obj_get(&value, \"{}\")?)?);",
+            "{} = Some(picoserde_wasm_bindgen::internal::obj_get(&value, \"{}\").and_then({}).map_err(|e| e.at_field(\"{}\"))?);",
             local_var,
+            js_field_name,
+            deserializer,
             js_field_name
         );
     }
@@ -119,17 +232,17 @@ pub fn derive_de_js_named(name: &str, defaults: bool, fields: &[Field]) -> Token
     r.parse().unwrap()
 }
 
-pub fn derive_de_js_proxy(proxy_type: &str, type_: &str) -> TokenStream {
+pub fn derive_de_js_proxy(proxy_type: &str, type_: &str, generics: &Generics) -> TokenStream {
     format!(
         "const _: () = {{
-            impl DeJs for {} {{
+            {} {{
                 fn de_js(value: JsValue) -> std::result::Result<Self, picoserde_wasm_bindgen::DeJsErr> {{
                     let proxy: {} = DeJs::de_js(value)?;
                     std::result::Result::Ok(Into::into(&proxy))
                 }}
             }}
         }};",
-        type_,
+        impl_header("DeJs", type_, generics, &[]),
         proxy_type
     )
         .parse()
@@ -145,13 +258,14 @@ pub fn derive_de_js_struct(struct_: &Struct) -> TokenStream {
 
     format!(
         "const _: () = {{
-            impl DeJs for {} {{
+            {} {{
                 fn de_js(value: JsValue) -> std::result::Result<Self, picoserde_wasm_bindgen::DeJsErr> {{
+                    picoserde_wasm_bindgen::internal::expect_object(&value)?;
                     std::result::Result::Ok({{ {} }})
                 }}
             }}
         }};",
-        struct_.name,
+        impl_header("DeJs", &struct_.name, &struct_.generics, &struct_.fields),
         body
     )
         .parse()
@@ -301,7 +415,7 @@ pub fn derive_de_js_enum(enum_: &Enum) -> TokenStream {
 
     format!(
         "const _: () = {{
-            impl DeJs for {} {{
+            {} {{
                 fn de_js(svalue: JsValue) -> std::result::Result<Self, picoserde_wasm_bindgen::DeJsErr> {{
                     // we are expecting an identifier
                     s.curly_open(i)?;
@@ -316,71 +430,184 @@ pub fn derive_de_js_enum(enum_: &Enum) -> TokenStream {
                 }}
             }}
         }};",
-        enum_.name,
+        impl_header(
+            "DeJs",
+            &enum_.name,
+            &enum_.generics,
+            enum_.variants.iter().flat_map(|variant| &variant.fields)
+        ),
         r
     )
         .parse()
         .unwrap()
 }
 
-// pub fn derive_ser_js_struct_unnamed(input: &DeriveInput, fields:&FieldsUnnamed) -> TokenStream {
-//     let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
-//     let bound = parse_quote!(SerJs);
-//     let bounded_where_clause = where_clause_with_bound(&input.generics, bound);
-//     let ident = &input.ident;
+/// Serializes a reference to the value of a tuple struct field, which can't be
+/// omitted like the ones of named structs.
+fn tuple_field_serializer(struct_: &Struct, field: &Field, value: &str) -> String {
+    let proxy = shared::attrs_proxy(&field.attributes);
+    if field.ty.is_double_option {
+        assert!(
+            proxy.is_none(),
+            "proxy is not supported on Option<Option<T>> fields"
+        );
+    }
+    let none_as = shared::attrs_none_as(&field.attributes)
+        .or_else(|| shared::attrs_none_as(&struct_.attributes));
+    if !field.ty.is_option || (proxy.is_none() && none_as.is_none()) {
+        return field_serializer(field, value);
+    }
+    let none = match none_as.as_deref() {
+        Some("null") => "JsValue::NULL",
+        Some("undefined") => "JsValue::UNDEFINED",
+        Some(_) => panic!("none_as = \"omit\" is not supported on tuple struct fields"),
+        None => "<Option<()> as SerJs>::ser_js(&None)",
+    };
+    format!(
+        "match {} {{ Some(t) => {}, None => {} }}",
+        value,
+        field_serializer(field, "t"),
+        none
+    )
+}
 
-//     let mut str_names = Vec::new();
-//     let last = fields.unnamed.len() - 1;
-//     for (index, field) in fields.unnamed.iter().enumerate() {
-//         let field_name = LitInt::new(&format!("{}", index), field.span());
-//         if index != last{
-//             str_names.push(quote!{
-//                 self.#field_name.ser_js(d, s);
-//                 s.out.push(',');
-//             })
-//         }
-//         else{
-//             str_names.push(quote!{
-//                 self.#field_name.ser_js(d, s);
-//             })
-//         }
-//     }
-//     quote! {
-//         impl #impl_generics SerJs for #ident #ty_generics #bounded_where_clause {
-//             fn ser_js(&self, d: usize, s: &mut makepad_tinyserde::SerJsState) {
-//                 s.out.push('[');
-//                 #(
-//                     #str_names
-//                 ) *
-//                 s.out.push(']');
-//             }
-//         }
-//     }
-// }
+/// Newtypes serialize as their inner value, other tuple structs as arrays.
+pub fn derive_ser_js_struct_unnamed(struct_: &Struct) -> TokenStream {
+    assert!(
+        shared::attrs_js_class(&struct_.attributes).is_none(),
+        "js_class is only supported on structs with named fields"
+    );
 
-// pub fn derive_de_js_struct_unnamed(input: &DeriveInput, fields:&FieldsUnnamed) -> TokenStream {
-//     let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
-//     let ident = &input.ident;
-//     let bound = parse_quote!(DeJs);
-//     let bounded_where_clause = where_clause_with_bound(&input.generics, bound);
+    let body = if struct_.fields.len() == 1 {
+        tuple_field_serializer(struct_, &struct_.fields[0], "&self.0")
+    } else {
+        let items: Vec<String> = struct_
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                tuple_field_serializer(struct_, field, &format!("&self.{}", index))
+            })
+            .collect();
+        format!(
+            "<[JsValue; {}] as SerJs>::ser_js(&[{}])",
+            items.len(),
+            items.join(", ")
+        )
+    };
+    let body = match shared::attrs_freeze(&struct_.attributes) {
+        Some(deep) => format!(
+            "picoserde_wasm_bindgen::internal::ser_frozen({}, || {{ {} }})",
+            deep, body
+        ),
+        None => body,
+    };
 
-//     let mut items = Vec::new();
-//     for _ in &fields.unnamed {
-//         items.push(quote!{{let r = DeJs::de_js(s,i)?;s.eat_comma_block(i)?;r}});
-//     }
+    format!(
+        "const _: () = {{
+            {} {{
+                fn ser_js(&self) -> JsValue {{
+                    {}
+                }}
+            }}
+        }};",
+        impl_header("SerJs", &struct_.name, &struct_.generics, &struct_.fields),
+        body
+    )
+        .parse()
+        .unwrap()
+}
 
-//     quote! {
-//         impl #impl_generics DeJs for #ident #ty_generics #bounded_where_clause {
-//             fn de_js(s: &mut makepad_tinyserde::DeJsState, i: &mut std::str::Chars) -> std::result::Result<Self,DeJsErr> {
-//                 s.block_open(i)?;
-//                 let r = Self(
-//                     #(
-//                         #items
-//                     ) *
-//                 );
-//                 s.block_close(i)?;
-//                 std::result::Result::Ok(r)
-//             }
-//         }
-//     }
-// }
\ No newline at end of file
+pub fn derive_de_js_struct_unnamed(struct_: &Struct) -> TokenStream {
+    let body = if struct_.fields.is_empty() {
+        "picoserde_wasm_bindgen::internal::array_items(value, 0)?;
+        Self()"
+            .to_string()
+    } else if struct_.fields.len() == 1 {
+        format!("Self({}(value)?)", field_deserializer(&struct_.fields[0]))
+    } else {
+        let items: Vec<String> = struct_
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                format!(
+                    "{}(items.next().unwrap()).map_err(|e| e.at_index({}))?",
+                    field_deserializer(field),
+                    index
+                )
+            })
+            .collect();
+        format!(
+            "let mut items = picoserde_wasm_bindgen::internal::array_items(value, {})?;
+            Self({})",
+            struct_.fields.len(),
+            items.join(", ")
+        )
+    };
+
+    format!(
+        "const _: () = {{
+            {} {{
+                fn de_js(value: JsValue) -> std::result::Result<Self, picoserde_wasm_bindgen::DeJsErr> {{
+                    std::result::Result::Ok({{ {} }})
+                }}
+            }}
+        }};",
+        impl_header("DeJs", &struct_.name, &struct_.generics, &struct_.fields),
+        body
+    )
+        .parse()
+        .unwrap()
+}
+
+/// Unit structs serialize as `null`, or `undefined` with `unit_as`.
+pub fn derive_ser_js_struct_unit(struct_: &Struct) -> TokenStream {
+    let value = match shared::attrs_unit_as(&struct_.attributes).as_deref() {
+        Some("undefined") => "JsValue::UNDEFINED",
+        _ => "JsValue::NULL",
+    };
+
+    format!(
+        "const _: () = {{
+            {} {{
+                fn ser_js(&self) -> JsValue {{
+                    {}
+                }}
+            }}
+        }};",
+        impl_header("SerJs", &struct_.name, &struct_.generics, &struct_.fields),
+        value
+    )
+        .parse()
+        .unwrap()
+}
+
+/// Unit structs deserialize from `null` or `undefined`, or from anything with
+/// `accept_any`.
+pub fn derive_de_js_struct_unit(struct_: &Struct) -> TokenStream {
+    let body = if shared::attrs_accept_any(&struct_.attributes) {
+        "std::result::Result::Ok(Self)"
+    } else {
+        "if value.is_null() || value.is_undefined() {
+            std::result::Result::Ok(Self)
+        } else {
+            std::result::Result::Err(picoserde_wasm_bindgen::DeJsErr::invalid_type(\"null or undefined\"))
+        }"
+    };
+
+    format!(
+        "const _: () = {{
+            {} {{
+                #[allow(unused_variables)]
+                fn de_js(value: JsValue) -> std::result::Result<Self, picoserde_wasm_bindgen::DeJsErr> {{
+                    {}
+                }}
+            }}
+        }};",
+        impl_header("DeJs", &struct_.name, &struct_.generics, &struct_.fields),
+        body
+    )
+        .parse()
+        .unwrap()
+}
@@ -28,8 +28,72 @@ pub fn attrs_rename(attributes: &[crate::parse::Attribute]) -> Option<String> {
     })
 }
 
+pub fn attrs_js_class(attributes: &[crate::parse::Attribute]) -> Option<String> {
+    attributes.iter().find_map(|attr| {
+        if attr.tokens.len() == 2 && attr.tokens[0] == "js_class" {
+            Some(attr.tokens[1].clone())
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns whether to freeze deeply, if at all.
+pub fn attrs_freeze(attributes: &[crate::parse::Attribute]) -> Option<bool> {
+    attributes.iter().find_map(|attr| match attr.tokens.first().map(String::as_str) {
+        Some("freeze") if attr.tokens.len() == 1 => Some(false),
+        Some("freeze") if attr.tokens.len() == 2 => match attr.tokens[1].as_str() {
+            "shallow" => Some(false),
+            "deep" => Some(true),
+            other => panic!(
+                "Unexpected freeze value \"{}\", expected \"shallow\" or \"deep\"",
+                other
+            ),
+        },
+        _ => None,
+    })
+}
+
 pub fn attrs_default(attributes: &[crate::parse::Attribute]) -> bool {
     attributes
         .iter()
         .any(|attr| attr.tokens.len() == 1 && attr.tokens[0] == "default".to_string())
-}
\ No newline at end of file
+}
+
+pub fn attrs_none_as(attributes: &[crate::parse::Attribute]) -> Option<String> {
+    attributes.iter().find_map(|attr| {
+        if attr.tokens.len() == 2 && attr.tokens[0] == "none_as" {
+            match attr.tokens[1].as_str() {
+                "null" | "undefined" | "omit" => Some(attr.tokens[1].clone()),
+                other => panic!(
+                    "Unexpected none_as value \"{}\", expected \"null\", \"undefined\" or \"omit\"",
+                    other
+                ),
+            }
+        } else {
+            None
+        }
+    })
+}
+
+pub fn attrs_unit_as(attributes: &[crate::parse::Attribute]) -> Option<String> {
+    attributes.iter().find_map(|attr| {
+        if attr.tokens.len() == 2 && attr.tokens[0] == "unit_as" {
+            match attr.tokens[1].as_str() {
+                "null" | "undefined" => Some(attr.tokens[1].clone()),
+                other => panic!(
+                    "Unexpected unit_as value \"{}\", expected \"null\" or \"undefined\"",
+                    other
+                ),
+            }
+        } else {
+            None
+        }
+    })
+}
+
+pub fn attrs_accept_any(attributes: &[crate::parse::Attribute]) -> bool {
+    attributes
+        .iter()
+        .any(|attr| attr.tokens.len() == 1 && attr.tokens[0] == "accept_any")
+}
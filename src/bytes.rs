@@ -0,0 +1,51 @@
+use js_sys::Uint8Array;
+use wasm_bindgen::{JsCast, JsValue};
+
+use super::{DeJs, DeJsErr, Result, SerJs};
+
+/// Copies bytes into a new `Uint8Array`.
+pub(crate) fn ser_bytes(bytes: &[u8]) -> JsValue {
+    Uint8Array::from(bytes).into()
+}
+
+/// Borrows the `Uint8Array` of bytes to deserialize.
+pub(crate) fn de_bytes(value: &JsValue) -> Result<&Uint8Array> {
+    value
+        .dyn_ref::<Uint8Array>()
+        .ok_or_else(|| DeJsErr::invalid_type("a Uint8Array"))
+}
+
+/// Proxy for bytes represented by a `Uint8Array` instead of an array of
+/// numbers:
+///
+/// ```rust,ignore
+/// #[picoserde(proxy = "ByteArray<Vec<u8>>")]
+/// pub payload: Vec<u8>,
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ByteArray<T>(pub T);
+
+impl SerJs for ByteArray<Vec<u8>> {
+    fn ser_js(&self) -> JsValue {
+        ser_bytes(&self.0)
+    }
+}
+
+impl DeJs for ByteArray<Vec<u8>> {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        Ok(ByteArray(de_bytes(&value)?.to_vec()))
+    }
+}
+
+impl From<&Vec<u8>> for ByteArray<Vec<u8>> {
+    fn from(bytes: &Vec<u8>) -> Self {
+        ByteArray(bytes.clone())
+    }
+}
+
+impl From<&ByteArray<Vec<u8>>> for Vec<u8> {
+    fn from(proxy: &ByteArray<Vec<u8>>) -> Self {
+        proxy.0.clone()
+    }
+}
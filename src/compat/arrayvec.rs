@@ -0,0 +1,30 @@
+use ::arrayvec::ArrayVec;
+use wasm_bindgen::JsValue;
+
+use crate::de::de_seq;
+use crate::ser::ser_seq;
+use crate::{DeJs, Result, SerJs};
+
+impl<T, const CAP: usize> SerJs for ArrayVec<T, CAP>
+    where
+        T: SerJs,
+{
+    fn ser_js(&self) -> JsValue {
+        ser_seq(self.iter())
+    }
+}
+
+/// Arrays longer than the capacity are rejected with an `InvalidLength` error.
+impl<T, const CAP: usize> DeJs for ArrayVec<T, CAP>
+    where
+        T: DeJs,
+{
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        let mut out = ArrayVec::new();
+        for item in de_seq(value, CAP)? {
+            out.push(item?);
+        }
+        Ok(out)
+    }
+}
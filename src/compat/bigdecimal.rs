@@ -0,0 +1,50 @@
+use std::str::FromStr;
+
+use ::bigdecimal::{BigDecimal, ToPrimitive};
+use wasm_bindgen::JsValue;
+
+use crate::decimal::de_decimal;
+use crate::{AsNumber, DeJs, Result, SerJs};
+
+fn de_big_decimal(value: JsValue) -> Result<BigDecimal> {
+    de_decimal(value, |s| BigDecimal::from_str(s).ok(), BigDecimal::to_f64)
+}
+
+/// Decimals are represented by a string to keep all their digits.
+impl SerJs for BigDecimal {
+    fn ser_js(&self) -> JsValue {
+        JsValue::from_str(&self.to_string())
+    }
+}
+
+impl DeJs for BigDecimal {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        de_big_decimal(value)
+    }
+}
+
+impl SerJs for AsNumber<BigDecimal> {
+    fn ser_js(&self) -> JsValue {
+        JsValue::from_f64(self.0.to_f64().unwrap_or(f64::NAN))
+    }
+}
+
+impl DeJs for AsNumber<BigDecimal> {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        de_big_decimal(value).map(AsNumber)
+    }
+}
+
+impl From<&BigDecimal> for AsNumber<BigDecimal> {
+    fn from(decimal: &BigDecimal) -> Self {
+        AsNumber(decimal.clone())
+    }
+}
+
+impl From<&AsNumber<BigDecimal>> for BigDecimal {
+    fn from(proxy: &AsNumber<BigDecimal>) -> Self {
+        proxy.0.clone()
+    }
+}
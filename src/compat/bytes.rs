@@ -0,0 +1,35 @@
+use ::bytes::{Bytes, BytesMut};
+use wasm_bindgen::JsValue;
+
+use crate::bytes::{de_bytes, ser_bytes};
+use crate::{DeJs, Result, SerJs};
+
+/// Bytes are represented by a `Uint8Array`, copied once in each direction.
+impl SerJs for Bytes {
+    fn ser_js(&self) -> JsValue {
+        ser_bytes(self)
+    }
+}
+
+impl DeJs for Bytes {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        Ok(Bytes::from(de_bytes(&value)?.to_vec()))
+    }
+}
+
+impl SerJs for BytesMut {
+    fn ser_js(&self) -> JsValue {
+        ser_bytes(self)
+    }
+}
+
+impl DeJs for BytesMut {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        let array = de_bytes(&value)?;
+        let mut bytes = BytesMut::zeroed(array.length() as usize);
+        array.copy_to(&mut bytes);
+        Ok(bytes)
+    }
+}
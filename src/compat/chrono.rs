@@ -0,0 +1,96 @@
+use ::chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use wasm_bindgen::JsValue;
+
+use crate::date::{de_epoch_millis, de_iso8601, ser_epoch_millis};
+use crate::{DeJs, DeJsErr, EpochMillis, Iso8601, JsDate, Result, SerJs};
+
+fn to_date_time(date: JsDate) -> Result<DateTime<Utc>> {
+    DateTime::from_timestamp_millis(date.epoch_millis() as i64)
+        .ok_or_else(|| DeJsErr::new("date out of the range of DateTime"))
+}
+
+impl SerJs for DateTime<Utc> {
+    fn ser_js(&self) -> JsValue {
+        ser_epoch_millis(self.timestamp_millis() as f64, |date| date.ser_js())
+    }
+}
+
+impl DeJs for DateTime<Utc> {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        to_date_time(JsDate::de_js(value)?)
+    }
+}
+
+impl SerJs for EpochMillis<DateTime<Utc>> {
+    fn ser_js(&self) -> JsValue {
+        ser_epoch_millis(self.0.timestamp_millis() as f64, |date| {
+            EpochMillis(date).ser_js()
+        })
+    }
+}
+
+impl DeJs for EpochMillis<DateTime<Utc>> {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        Ok(EpochMillis(to_date_time(de_epoch_millis(value)?)?))
+    }
+}
+
+impl SerJs for Iso8601<DateTime<Utc>> {
+    fn ser_js(&self) -> JsValue {
+        JsValue::from_str(&self.0.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+}
+
+impl DeJs for Iso8601<DateTime<Utc>> {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        match DateTime::parse_from_rfc3339(&de_iso8601(value)?) {
+            Ok(date_time) => Ok(Iso8601(date_time.with_timezone(&Utc))),
+            Err(err) => Err(DeJsErr::new(format_args!("invalid ISO-8601 date: {}", err))),
+        }
+    }
+}
+
+impl From<&DateTime<Utc>> for EpochMillis<DateTime<Utc>> {
+    fn from(date_time: &DateTime<Utc>) -> Self {
+        EpochMillis(*date_time)
+    }
+}
+
+impl From<&EpochMillis<DateTime<Utc>>> for DateTime<Utc> {
+    fn from(proxy: &EpochMillis<DateTime<Utc>>) -> Self {
+        proxy.0
+    }
+}
+
+impl From<&DateTime<Utc>> for Iso8601<DateTime<Utc>> {
+    fn from(date_time: &DateTime<Utc>) -> Self {
+        Iso8601(*date_time)
+    }
+}
+
+impl From<&Iso8601<DateTime<Utc>>> for DateTime<Utc> {
+    fn from(proxy: &Iso8601<DateTime<Utc>>) -> Self {
+        proxy.0
+    }
+}
+
+/// Calendar dates are represented by a `YYYY-MM-DD` string.
+impl SerJs for NaiveDate {
+    fn ser_js(&self) -> JsValue {
+        JsValue::from_str(&self.format("%Y-%m-%d").to_string())
+    }
+}
+
+impl DeJs for NaiveDate {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        match value.as_string() {
+            Some(s) => NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+                .map_err(|err| DeJsErr::new(format_args!("invalid date: {}", err))),
+            None => Err(DeJsErr::invalid_type("a YYYY-MM-DD date string")),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::hash::{BuildHasher, Hash};
+
+use ::hashbrown::HashMap;
+use wasm_bindgen::JsValue;
+
+use crate::de::de_map;
+use crate::ser::ser_map;
+use crate::{DeJs, Result, SerJs};
+
+impl<K, V, S> SerJs for HashMap<K, V, S>
+    where
+        K: SerJs,
+        V: SerJs,
+{
+    fn ser_js(&self) -> JsValue {
+        ser_map(self)
+    }
+}
+
+impl<K, V, S> DeJs for HashMap<K, V, S>
+    where
+        K: DeJs + Eq + Hash,
+        V: DeJs,
+        S: BuildHasher + Default,
+{
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        let mut map = HashMap::with_hasher(S::default());
+        de_map(value, |k, v| {
+            map.insert(k, v);
+        })?;
+        Ok(map)
+    }
+}
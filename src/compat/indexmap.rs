@@ -0,0 +1,56 @@
+use std::hash::{BuildHasher, Hash};
+
+use ::indexmap::{IndexMap, IndexSet};
+use wasm_bindgen::JsValue;
+
+use crate::de::de_map;
+use crate::ser::{ser_map, ser_seq};
+use crate::{DeJs, Result, SerJs};
+
+/// Maps are represented by an object with their entries in the same order.
+impl<K, V, S> SerJs for IndexMap<K, V, S>
+    where
+        K: SerJs,
+        V: SerJs,
+{
+    fn ser_js(&self) -> JsValue {
+        ser_map(self)
+    }
+}
+
+impl<K, V, S> DeJs for IndexMap<K, V, S>
+    where
+        K: DeJs + Eq + Hash,
+        V: DeJs,
+        S: BuildHasher + Default,
+{
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        let mut map = IndexMap::with_hasher(S::default());
+        de_map(value, |k, v| {
+            map.insert(k, v);
+        })?;
+        Ok(map)
+    }
+}
+
+/// Sets are represented by an array with their items in the same order.
+impl<T, S> SerJs for IndexSet<T, S>
+    where
+        T: SerJs,
+{
+    fn ser_js(&self) -> JsValue {
+        ser_seq(self.iter())
+    }
+}
+
+impl<T, S> DeJs for IndexSet<T, S>
+    where
+        T: DeJs + Eq + Hash,
+        S: BuildHasher + Default,
+{
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        Ok(Vec::<T>::de_js(value)?.into_iter().collect())
+    }
+}
@@ -0,0 +1,51 @@
+use std::str::FromStr;
+
+use ::rust_decimal::prelude::ToPrimitive;
+use ::rust_decimal::Decimal;
+use wasm_bindgen::JsValue;
+
+use crate::decimal::de_decimal;
+use crate::{AsNumber, DeJs, Result, SerJs};
+
+fn de_rust_decimal(value: JsValue) -> Result<Decimal> {
+    de_decimal(value, |s| Decimal::from_str(s).ok(), Decimal::to_f64)
+}
+
+/// Decimals are represented by a string to keep all their digits.
+impl SerJs for Decimal {
+    fn ser_js(&self) -> JsValue {
+        JsValue::from_str(&self.to_string())
+    }
+}
+
+impl DeJs for Decimal {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        de_rust_decimal(value)
+    }
+}
+
+impl SerJs for AsNumber<Decimal> {
+    fn ser_js(&self) -> JsValue {
+        JsValue::from_f64(self.0.to_f64().unwrap_or(f64::NAN))
+    }
+}
+
+impl DeJs for AsNumber<Decimal> {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        de_rust_decimal(value).map(AsNumber)
+    }
+}
+
+impl From<&Decimal> for AsNumber<Decimal> {
+    fn from(decimal: &Decimal) -> Self {
+        AsNumber(*decimal)
+    }
+}
+
+impl From<&AsNumber<Decimal>> for Decimal {
+    fn from(proxy: &AsNumber<Decimal>) -> Self {
+        proxy.0
+    }
+}
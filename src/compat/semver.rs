@@ -0,0 +1,21 @@
+use ::semver::Version;
+use wasm_bindgen::JsValue;
+
+use crate::{DeJs, DeJsErr, Result, SerJs};
+
+impl SerJs for Version {
+    fn ser_js(&self) -> JsValue {
+        JsValue::from_str(&self.to_string())
+    }
+}
+
+impl DeJs for Version {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        match value.as_string() {
+            Some(s) => Version::parse(&s)
+                .map_err(|err| DeJsErr::new(format_args!("invalid version: {}", err))),
+            None => Err(DeJsErr::invalid_type("a version string")),
+        }
+    }
+}
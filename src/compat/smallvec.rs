@@ -0,0 +1,32 @@
+use ::smallvec::{Array, SmallVec};
+use wasm_bindgen::JsValue;
+
+use crate::de::{cautious_capacity, de_seq};
+use crate::ser::ser_seq;
+use crate::{DeJs, Result, SerJs};
+
+impl<A> SerJs for SmallVec<A>
+    where
+        A: Array,
+        A::Item: SerJs,
+{
+    fn ser_js(&self) -> JsValue {
+        ser_seq(self.iter())
+    }
+}
+
+impl<A> DeJs for SmallVec<A>
+    where
+        A: Array,
+        A::Item: DeJs,
+{
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        let items = de_seq(value, usize::MAX)?;
+        let mut out = SmallVec::with_capacity(cautious_capacity(items.len()));
+        for item in items {
+            out.push(item?);
+        }
+        Ok(out)
+    }
+}
@@ -0,0 +1,113 @@
+use ::time::format_description::well_known::Rfc3339;
+use ::time::format_description::FormatItem;
+use ::time::macros::format_description;
+use ::time::{Date, OffsetDateTime};
+use wasm_bindgen::JsValue;
+
+use crate::date::{de_epoch_millis, de_iso8601, ser_epoch_millis};
+use crate::{DeJs, DeJsErr, EpochMillis, Iso8601, JsDate, Result, SerJs, ser_error};
+
+fn epoch_millis(date_time: &OffsetDateTime) -> f64 {
+    (date_time.unix_timestamp_nanos() / 1_000_000) as f64
+}
+
+fn to_date_time(date: JsDate) -> Result<OffsetDateTime> {
+    OffsetDateTime::from_unix_timestamp_nanos(date.epoch_millis() as i128 * 1_000_000)
+        .map_err(|_| DeJsErr::new("date out of the range of OffsetDateTime"))
+}
+
+impl SerJs for OffsetDateTime {
+    fn ser_js(&self) -> JsValue {
+        ser_epoch_millis(epoch_millis(self), |date| date.ser_js())
+    }
+}
+
+impl DeJs for OffsetDateTime {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        to_date_time(JsDate::de_js(value)?)
+    }
+}
+
+impl SerJs for EpochMillis<OffsetDateTime> {
+    fn ser_js(&self) -> JsValue {
+        ser_epoch_millis(epoch_millis(&self.0), |date| EpochMillis(date).ser_js())
+    }
+}
+
+impl DeJs for EpochMillis<OffsetDateTime> {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        Ok(EpochMillis(to_date_time(de_epoch_millis(value)?)?))
+    }
+}
+
+impl SerJs for Iso8601<OffsetDateTime> {
+    fn ser_js(&self) -> JsValue {
+        match self.0.format(&Rfc3339) {
+            Ok(s) => JsValue::from_str(&s),
+            Err(err) => crate::ser_error(DeJsErr::new(format_args!(
+                "date can't be formatted as ISO-8601: {}",
+                err
+            ))),
+        }
+    }
+}
+
+impl DeJs for Iso8601<OffsetDateTime> {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        match OffsetDateTime::parse(&de_iso8601(value)?, &Rfc3339) {
+            Ok(date_time) => Ok(Iso8601(date_time)),
+            Err(err) => Err(DeJsErr::new(format_args!("invalid ISO-8601 date: {}", err))),
+        }
+    }
+}
+
+impl From<&OffsetDateTime> for EpochMillis<OffsetDateTime> {
+    fn from(date_time: &OffsetDateTime) -> Self {
+        EpochMillis(*date_time)
+    }
+}
+
+impl From<&EpochMillis<OffsetDateTime>> for OffsetDateTime {
+    fn from(proxy: &EpochMillis<OffsetDateTime>) -> Self {
+        proxy.0
+    }
+}
+
+impl From<&OffsetDateTime> for Iso8601<OffsetDateTime> {
+    fn from(date_time: &OffsetDateTime) -> Self {
+        Iso8601(*date_time)
+    }
+}
+
+impl From<&Iso8601<OffsetDateTime>> for OffsetDateTime {
+    fn from(proxy: &Iso8601<OffsetDateTime>) -> Self {
+        proxy.0
+    }
+}
+
+const DATE_FORMAT: &[FormatItem<'static>] = format_description!("[year]-[month]-[day]");
+
+/// Calendar dates are represented by a `YYYY-MM-DD` string, with a `-` before
+/// the year for years before 0.
+impl SerJs for Date {
+    fn ser_js(&self) -> JsValue {
+        match self.format(DATE_FORMAT) {
+            Ok(s) => JsValue::from_str(&s),
+            Err(err) => ser_error(DeJsErr::new(err)),
+        }
+    }
+}
+
+impl DeJs for Date {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        match value.as_string() {
+            Some(s) => Date::parse(&s, DATE_FORMAT)
+                .map_err(|_| DeJsErr::new(format_args!("invalid date: {}", s))),
+            None => Err(DeJsErr::invalid_type("a YYYY-MM-DD date string")),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use ::tinyvec::{Array, ArrayVec, TinyVec};
+use wasm_bindgen::JsValue;
+
+use crate::de::{cautious_capacity, de_seq};
+use crate::ser::ser_seq;
+use crate::{DeJs, Result, SerJs};
+
+impl<A> SerJs for ArrayVec<A>
+    where
+        A: Array,
+        A::Item: SerJs,
+{
+    fn ser_js(&self) -> JsValue {
+        ser_seq(self.iter())
+    }
+}
+
+/// Arrays longer than the capacity are rejected with an `InvalidLength` error.
+impl<A> DeJs for ArrayVec<A>
+    where
+        A: Array,
+        A::Item: DeJs,
+{
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        let mut out = ArrayVec::new();
+        for item in de_seq(value, A::CAPACITY)? {
+            out.push(item?);
+        }
+        Ok(out)
+    }
+}
+
+impl<A> SerJs for TinyVec<A>
+    where
+        A: Array,
+        A::Item: SerJs,
+{
+    fn ser_js(&self) -> JsValue {
+        ser_seq(self.iter())
+    }
+}
+
+impl<A> DeJs for TinyVec<A>
+    where
+        A: Array,
+        A::Item: DeJs,
+{
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        let items = de_seq(value, usize::MAX)?;
+        let mut out = TinyVec::with_capacity(cautious_capacity(items.len()));
+        for item in items {
+            out.push(item?);
+        }
+        Ok(out)
+    }
+}
@@ -0,0 +1,22 @@
+use ::url::Url;
+use wasm_bindgen::JsValue;
+
+use crate::{DeJs, DeJsErr, Result, SerJs};
+
+impl SerJs for Url {
+    fn ser_js(&self) -> JsValue {
+        self.as_str().ser_js()
+    }
+}
+
+impl DeJs for Url {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        match value.as_string() {
+            Some(s) => {
+                Url::parse(&s).map_err(|err| DeJsErr::new(format_args!("invalid URL: {}", err)))
+            }
+            None => Err(DeJsErr::invalid_type("a URL string")),
+        }
+    }
+}
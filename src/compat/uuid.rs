@@ -0,0 +1,54 @@
+use ::uuid::Uuid;
+use wasm_bindgen::JsValue;
+
+use crate::bytes::{de_bytes, ser_bytes};
+use crate::{ByteArray, DeJs, DeJsErr, Result, SerJs};
+
+/// UUIDs are represented by a hyphenated string.
+impl SerJs for Uuid {
+    fn ser_js(&self) -> JsValue {
+        JsValue::from_str(self.hyphenated().encode_lower(&mut Uuid::encode_buffer()))
+    }
+}
+
+impl DeJs for Uuid {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        match value.as_string() {
+            Some(s) => Uuid::parse_str(&s)
+                .map_err(|err| DeJsErr::new(format_args!("invalid UUID: {}", err))),
+            None => Err(DeJsErr::invalid_type("a UUID string")),
+        }
+    }
+}
+
+impl SerJs for ByteArray<Uuid> {
+    fn ser_js(&self) -> JsValue {
+        ser_bytes(self.0.as_bytes())
+    }
+}
+
+impl DeJs for ByteArray<Uuid> {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        let array = de_bytes(&value)?;
+        if array.length() != 16 {
+            return Err(DeJsErr::invalid_length(array.length(), "16 bytes of a UUID"));
+        }
+        let mut bytes = [0; 16];
+        array.copy_to(&mut bytes);
+        Ok(ByteArray(Uuid::from_bytes(bytes)))
+    }
+}
+
+impl From<&Uuid> for ByteArray<Uuid> {
+    fn from(uuid: &Uuid) -> Self {
+        ByteArray(*uuid)
+    }
+}
+
+impl From<&ByteArray<Uuid>> for Uuid {
+    fn from(proxy: &ByteArray<Uuid>) -> Self {
+        proxy.0
+    }
+}
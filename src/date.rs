@@ -0,0 +1,251 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use js_sys::Date;
+use wasm_bindgen::{JsCast, JsValue};
+
+use super::{DeJs, DeJsErr, Result, SerJs, ser_error};
+
+/// Largest distance from the epoch a JavaScript `Date` can represent.
+const MAX_DATE_MILLIS: f64 = 8.64e15;
+
+/// A point in time as held by a JavaScript `Date`, in milliseconds since the
+/// Unix epoch.
+///
+/// It serializes into a `Date` and deserializes from one, rejecting invalid
+/// dates.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct JsDate(f64);
+
+impl JsDate {
+    /// Creates a date from milliseconds since the Unix epoch, or returns
+    /// `None` if it is out of the range of JavaScript dates.
+    pub fn from_epoch_millis(millis: f64) -> Option<JsDate> {
+        if millis.abs() <= MAX_DATE_MILLIS {
+            // JavaScript drops fractions of milliseconds the same way.
+            Some(JsDate(millis.trunc()))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the current time.
+    pub fn now() -> JsDate {
+        JsDate(Date::now())
+    }
+
+    /// Returns the milliseconds since the Unix epoch.
+    pub fn epoch_millis(self) -> f64 {
+        self.0
+    }
+
+    /// Converts a `SystemTime`, or returns `None` if it is out of the range
+    /// of JavaScript dates.
+    pub fn from_system_time(time: SystemTime) -> Option<JsDate> {
+        JsDate::from_epoch_millis(system_time_millis(time))
+    }
+
+    /// Converts into a `SystemTime`, or returns `None` if the platform can't
+    /// represent it, which some do for times before the Unix epoch.
+    pub fn to_system_time(self) -> Option<SystemTime> {
+        let offset = Duration::from_millis(self.0.abs() as u64);
+        if self.0 >= 0.0 {
+            UNIX_EPOCH.checked_add(offset)
+        } else {
+            UNIX_EPOCH.checked_sub(offset)
+        }
+    }
+}
+
+impl SerJs for JsDate {
+    fn ser_js(&self) -> JsValue {
+        Date::new(&JsValue::from_f64(self.0)).into()
+    }
+}
+
+impl DeJs for JsDate {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        match value.dyn_ref::<Date>() {
+            Some(date) => JsDate::from_epoch_millis(date.get_time())
+                .ok_or_else(|| DeJsErr::new("invalid date")),
+            None => Err(DeJsErr::invalid_type("a Date")),
+        }
+    }
+}
+
+/// Converts milliseconds since the Unix epoch into a date, reporting times out
+/// of the range of JavaScript dates.
+pub(crate) fn ser_epoch_millis(millis: f64, ser: impl FnOnce(JsDate) -> JsValue) -> JsValue {
+    match JsDate::from_epoch_millis(millis) {
+        Some(date) => ser(date),
+        None => ser_error(DeJsErr::new("time out of the range of JavaScript dates")),
+    }
+}
+
+fn system_time_millis(time: SystemTime) -> f64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => after.as_millis() as f64,
+        Err(before) => -(before.duration().as_millis() as f64),
+    }
+}
+
+fn ser_system_time(time: &SystemTime, ser: impl FnOnce(JsDate) -> JsValue) -> JsValue {
+    ser_epoch_millis(system_time_millis(*time), ser)
+}
+
+fn to_system_time(date: JsDate) -> Result<SystemTime> {
+    date.to_system_time()
+        .ok_or_else(|| DeJsErr::new("date out of the range of SystemTime on this platform"))
+}
+
+impl SerJs for SystemTime {
+    fn ser_js(&self) -> JsValue {
+        ser_system_time(self, |date| date.ser_js())
+    }
+}
+
+impl DeJs for SystemTime {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        to_system_time(JsDate::de_js(value)?)
+    }
+}
+
+/// Proxy for times represented by a number of milliseconds since the Unix
+/// epoch instead of a `Date`:
+///
+/// ```rust,ignore
+/// #[picoserde(proxy = "EpochMillis<SystemTime>")]
+/// pub modified: SystemTime,
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct EpochMillis<T>(pub T);
+
+/// Deserializes milliseconds since the Unix epoch into a date.
+pub(crate) fn de_epoch_millis(value: JsValue) -> Result<JsDate> {
+    match value.as_f64() {
+        Some(millis) => {
+            JsDate::from_epoch_millis(millis).ok_or_else(|| DeJsErr::new("invalid date"))
+        }
+        None => Err(DeJsErr::invalid_type("a number of milliseconds")),
+    }
+}
+
+impl SerJs for EpochMillis<JsDate> {
+    fn ser_js(&self) -> JsValue {
+        JsValue::from_f64(self.0.epoch_millis())
+    }
+}
+
+impl DeJs for EpochMillis<JsDate> {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        Ok(EpochMillis(de_epoch_millis(value)?))
+    }
+}
+
+impl SerJs for EpochMillis<SystemTime> {
+    fn ser_js(&self) -> JsValue {
+        ser_system_time(&self.0, |date| EpochMillis(date).ser_js())
+    }
+}
+
+impl DeJs for EpochMillis<SystemTime> {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        Ok(EpochMillis(to_system_time(de_epoch_millis(value)?)?))
+    }
+}
+
+impl From<&SystemTime> for EpochMillis<SystemTime> {
+    fn from(time: &SystemTime) -> Self {
+        EpochMillis(*time)
+    }
+}
+
+impl From<&EpochMillis<SystemTime>> for SystemTime {
+    fn from(proxy: &EpochMillis<SystemTime>) -> Self {
+        proxy.0
+    }
+}
+
+/// Proxy for times represented by an ISO-8601 string instead of a `Date`:
+///
+/// ```rust,ignore
+/// #[picoserde(proxy = "Iso8601<JsDate>")]
+/// pub modified: JsDate,
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Iso8601<T>(pub T);
+
+/// Deserializes the string of an ISO-8601 date, leaving parsing to the caller.
+pub(crate) fn de_iso8601(value: JsValue) -> Result<String> {
+    value
+        .as_string()
+        .ok_or_else(|| DeJsErr::invalid_type("an ISO-8601 date string"))
+}
+
+impl SerJs for Iso8601<JsDate> {
+    fn ser_js(&self) -> JsValue {
+        Date::new(&JsValue::from_f64(self.0.epoch_millis()))
+            .to_iso_string()
+            .into()
+    }
+}
+
+impl DeJs for Iso8601<JsDate> {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        let millis = Date::parse(&de_iso8601(value)?);
+        JsDate::from_epoch_millis(millis)
+            .map(Iso8601)
+            .ok_or_else(|| DeJsErr::new("invalid ISO-8601 date"))
+    }
+}
+
+impl From<&JsDate> for Iso8601<JsDate> {
+    fn from(date: &JsDate) -> Self {
+        Iso8601(*date)
+    }
+}
+
+impl From<&Iso8601<JsDate>> for JsDate {
+    fn from(proxy: &Iso8601<JsDate>) -> Self {
+        proxy.0
+    }
+}
+
+impl From<&JsDate> for EpochMillis<JsDate> {
+    fn from(date: &JsDate) -> Self {
+        EpochMillis(*date)
+    }
+}
+
+impl From<&EpochMillis<JsDate>> for JsDate {
+    fn from(proxy: &EpochMillis<JsDate>) -> Self {
+        proxy.0
+    }
+}
+
+/// Durations are represented by a number of milliseconds.
+impl SerJs for Duration {
+    fn ser_js(&self) -> JsValue {
+        JsValue::from_f64(self.as_secs() as f64 * 1000.0 + self.subsec_nanos() as f64 / 1e6)
+    }
+}
+
+impl DeJs for Duration {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        match value.as_f64() {
+            Some(millis) if millis >= 0.0 && millis < u64::MAX as f64 => {
+                let nanos = (millis.fract() * 1e6).round() as u64;
+                Ok(Duration::from_millis(millis as u64) + Duration::from_nanos(nanos))
+            }
+            Some(_) => Err(DeJsErr::new(
+                "invalid duration, expected a non-negative number of milliseconds",
+            )),
+            None => Err(DeJsErr::invalid_type("a number of milliseconds")),
+        }
+    }
+}
@@ -1,26 +1,98 @@
+use std::any::Any;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
-use std::hash::Hash;
+use std::convert::TryInto;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, RwLock};
 
 //use js_sys::{Array, ArrayBuffer, JsString, Number, Object, Uint8Array};
-use js_sys::{Array, Object};
-use wasm_bindgen::{JsCast, JsValue};
+use js_sys::{Array, Map, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 
-use super::{Result, static_str_to_js};
+use super::{DeJsErr, Result, static_str_to_js};
 
 pub mod internal {
     use wasm_bindgen::prelude::*;
 
-    use super::{Result, static_str_to_js};
+    use super::{DeJs, DeJsErr, Result, static_str_to_js};
 
     #[inline]
     pub fn label(label: &'static str) -> JsValue {
         static_str_to_js(label)
     }
 
+    /// Reads the items of the array a tuple struct of `len` fields is
+    /// deserialized from.
+    #[inline]
+    pub fn array_items(value: JsValue, len: usize) -> Result<std::vec::IntoIter<JsValue>> {
+        let items = super::de_seq::<JsValue>(value, len)?;
+        if items.len() != len {
+            return Err(DeJsErr::invalid_length(
+                items.len() as u32,
+                &format!("an array of {} items", len),
+            ));
+        }
+        Ok(items.collect::<Result<Vec<_>>>()?.into_iter())
+    }
+
+    /// Checks that a struct is deserialized from an object, `Proxy`s included.
+    #[inline]
+    pub fn expect_object(value: &JsValue) -> Result<()> {
+        if value.is_object() {
+            Ok(())
+        } else {
+            Err(DeJsErr::invalid_type("an object"))
+        }
+    }
+
+    /// Reads a property, returning exceptions thrown by getters and `Proxy`
+    /// traps as errors.
     #[inline]
     pub fn obj_get(value: &JsValue, key: &'static str) -> Result<JsValue> {
         let key = label(key);
-        Ok(js_sys::Reflect::get(value, &key).unwrap())
+        if super::options().own_properties_only {
+            return super::own_data_property(value, &key);
+        }
+        Ok(js_sys::Reflect::get(value, &key)?)
+    }
+
+    /// Deserializes a field through its `#[picoserde(proxy = "")]` type.
+    #[inline]
+    pub fn de_proxy<P, T>(value: JsValue) -> Result<T>
+        where
+            P: DeJs,
+            T: for<'a> From<&'a P>,
+    {
+        let proxy: P = DeJs::de_js(value)?;
+        Ok(From::from(&proxy))
+    }
+
+    /// Deserializes an `Option` field through its `#[picoserde(proxy = "")]` type.
+    #[inline]
+    pub fn de_option_proxy<P, T>(value: JsValue) -> Result<Option<T>>
+        where
+            P: DeJs,
+            T: for<'a> From<&'a P>,
+    {
+        let proxy: Option<P> = DeJs::de_js(value)?;
+        Ok(proxy.as_ref().map(From::from))
+    }
+
+    /// Deserializes an `Option<Option<T>>` field, where `undefined` stands
+    /// for an absent value and `null` for `Some(None)`.
+    #[inline]
+    pub fn de_double_option<T: DeJs>(value: JsValue) -> Result<Option<Option<T>>> {
+        if value.is_undefined() {
+            Ok(None)
+        } else if value.is_null() {
+            Ok(Some(None))
+        } else {
+            Ok(Some(Some(DeJs::de_js(value)?)))
+        }
     }
 }
 
@@ -28,19 +100,225 @@ pub trait DeJs: Sized {
     fn de_js(value: JsValue) -> Result<Self>;
 }
 
+/// Deserializer options, for when the defaults of
+/// [`from_value`](crate::from_value) don't fit.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Deserializer {
+    preserve_references: bool,
+    results_with_ok_flag: bool,
+    own_properties_only: bool,
+    reject_prototype_keys: bool,
+}
+
+impl Deserializer {
+    /// Creates a deserializer with the default options.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Deserializes the same JavaScript object into the same `Rc` or `Arc`
+    /// every time it is encountered, instead of separate copies.
+    pub fn preserve_references(mut self, value: bool) -> Self {
+        self.preserve_references = value;
+        self
+    }
+
+    /// Deserializes `Result`s from `{ ok: true, value }` and
+    /// `{ ok: false, error }` instead of `{ Ok: value }` and `{ Err: error }`.
+    pub fn results_with_ok_flag(mut self, value: bool) -> Self {
+        self.results_with_ok_flag = value;
+        self
+    }
+
+    /// Reads only own data properties of objects and arrays, for untrusted
+    /// input: fields and array items inherited from a polluted prototype are
+    /// treated as absent, and getters are rejected instead of being run.
+    ///
+    /// This sets [`reject_prototype_keys`](Self::reject_prototype_keys) to the
+    /// same value, which can be changed afterwards.
+    pub fn own_properties_only(mut self, value: bool) -> Self {
+        self.own_properties_only = value;
+        self.reject_prototype_keys = value;
+        self
+    }
+
+    /// Rejects the `__proto__`, `constructor` and `prototype` keys of maps,
+    /// which are easily misused once the map is back in JavaScript. Enabled
+    /// along with [`own_properties_only`](Self::own_properties_only).
+    pub fn reject_prototype_keys(mut self, value: bool) -> Self {
+        self.reject_prototype_keys = value;
+        self
+    }
+
+    /// Converts a [`JsValue`] into a Rust type using these options.
+    pub fn deserialize<T: DeJs>(&self, value: JsValue) -> Result<T> {
+        // Keep the state of an outer deserialization if this one is nested in it.
+        let _guard = StateGuard(STATE.with(|state| state.replace(State::new(*self))));
+        T::de_js(value)
+    }
+}
+
+/// Restores the state of an outer deserialization, even on panic.
+struct StateGuard(State);
+
+impl Drop for StateGuard {
+    fn drop(&mut self) {
+        let outer = std::mem::take(&mut self.0);
+        STATE.with(|state| state.replace(outer));
+    }
+}
+
+#[derive(Default)]
+struct State {
+    options: Deserializer,
+    /// Indices into `shared` of JavaScript objects deserialized into shared
+    /// pointers, with `preserve_references`.
+    indices: Option<Map>,
+    /// Shared pointers, or `None` while they are still being deserialized.
+    shared: Vec<Option<Box<dyn Any>>>,
+}
+
+impl State {
+    fn new(options: Deserializer) -> Self {
+        State {
+            options,
+            ..Default::default()
+        }
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<State> = Default::default();
+}
+
+fn options() -> Deserializer {
+    STATE.with(|state| state.borrow().options)
+}
+
+/// Deserializes a shared pointer, reusing the one created for the same
+/// JavaScript object before if `preserve_references` is enabled.
+fn de_shared<P: Clone + 'static>(
+    value: JsValue,
+    de: impl FnOnce(JsValue) -> Result<P>,
+) -> Result<P> {
+    enum Lookup<P> {
+        Untracked,
+        Seen(P),
+        Unseen(usize),
+    }
+
+    if !value.is_object() {
+        return de(value);
+    }
+
+    let lookup = STATE.with(|state| {
+        let State {
+            options,
+            indices,
+            shared,
+        } = &mut *state.borrow_mut();
+        if !options.preserve_references {
+            return Ok(Lookup::Untracked);
+        }
+        let indices = indices.get_or_insert_with(Map::new);
+        if let Some(index) = indices.get(&value).as_f64() {
+            return match &shared[index as usize] {
+                // A different type deserialized from the same object can't be
+                // shared, so it gets its own copy.
+                Some(ptr) => Ok(match ptr.downcast_ref::<P>() {
+                    Some(ptr) => Lookup::Seen(ptr.clone()),
+                    None => Lookup::Untracked,
+                }),
+                None => Err(DeJsErr::new("cycle detected in shared references")),
+            };
+        }
+        indices.set(&value, &JsValue::from_f64(shared.len() as f64));
+        shared.push(None);
+        Ok(Lookup::Unseen(shared.len() - 1))
+    })?;
+
+    match lookup {
+        Lookup::Untracked => de(value),
+        Lookup::Seen(ptr) => Ok(ptr),
+        Lookup::Unseen(index) => {
+            let ptr = de(value)?;
+            STATE.with(|state| {
+                state.borrow_mut().shared[index] = Some(Box::new(ptr.clone()));
+            });
+            Ok(ptr)
+        }
+    }
+}
+
+/// Bindings for the `js_sys` functions that can run getters or `Proxy` traps,
+/// returning their exceptions instead of aborting.
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = Object, js_name = keys, catch)]
+    fn object_keys(object: &JsValue) -> std::result::Result<Array, JsValue>;
+
+    #[wasm_bindgen(js_namespace = Object, js_name = getOwnPropertyDescriptor, catch)]
+    fn get_own_property_descriptor(
+        object: &JsValue,
+        key: &JsValue,
+    ) -> std::result::Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_namespace = Object, js_name = hasOwn, catch)]
+    fn has_own(object: &JsValue, key: &JsValue) -> std::result::Result<bool, JsValue>;
+}
+
+/// Reads an own data property of an object, without looking up the prototype
+/// chain or running getters.
+fn own_data_property(value: &JsValue, key: &JsValue) -> Result<JsValue> {
+    if !value.is_object() {
+        return Err(DeJsErr::invalid_type("an object"));
+    }
+    let descriptor = get_own_property_descriptor(value, key)?;
+    if descriptor.is_undefined() {
+        Ok(JsValue::UNDEFINED)
+    } else if Reflect::has(&descriptor, &internal::label("get"))? {
+        Err(DeJsErr::new("accessor properties are not deserialized with own_properties_only"))
+    } else {
+        Ok(Reflect::get(&descriptor, &internal::label("value"))?)
+    }
+}
+
+/// Reads an item of an array, without running index getters or reading holes
+/// from `Array.prototype` with `own_properties_only`.
+fn array_item(array: &Array, index: u32, own_properties_only: bool) -> Result<JsValue> {
+    if own_properties_only {
+        own_data_property(array, &JsValue::from(index))
+    } else {
+        Ok(Reflect::get_u32(array, index)?)
+    }
+}
+
+/// Checks whether an object has a property, honoring `own_properties_only`.
+fn has_property(value: &JsValue, key: &JsValue) -> Result<bool> {
+    if options().own_properties_only {
+        Ok(has_own(value, key)?)
+    } else {
+        Ok(Reflect::has(value, key)?)
+    }
+}
+
 fn is_nullish(value: &JsValue) -> bool {
     value.is_null() || value.is_undefined()
 }
 
+fn out_of_range(value: f64, ty: &str) -> DeJsErr {
+    DeJsErr::new(format_args!("number {} out of range for {}", value, ty))
+}
+
 macro_rules! impl_ser_de_js_unsigned {
     ( $ ty: ident, $ max: expr) => {
         impl DeJs for $ty {
             #[inline]
             fn de_js(value: JsValue) -> Result<$ty> {
                 match value.as_f64() {
-                    Some(v) if (v as u64) > ($max as u64) => unimplemented!(),
+                    Some(v) if !(v >= 0.0 && v <= $max as f64) => Err(out_of_range(v, stringify!($ty))),
                     Some(v) => Ok(v as $ty),
-                    None => unimplemented!(),
+                    None => Err(DeJsErr::invalid_type("a number")),
                 }
             }
         }
@@ -53,9 +331,11 @@ macro_rules! impl_ser_de_js_signed {
             #[inline]
             fn de_js(value: JsValue) -> Result<$ty> {
                 match value.as_f64() {
-                    Some(v) if (v as i64) < ($min as i64) || (v as i64) > ($max as i64) => unimplemented!(),
+                    Some(v) if !(v >= $min as f64 && v <= $max as f64) => {
+                        Err(out_of_range(v, stringify!($ty)))
+                    }
                     Some(v) => Ok(v as $ty),
-                    None => unimplemented!(),
+                    None => Err(DeJsErr::invalid_type("a number")),
                 }
             }
         }
@@ -69,7 +349,7 @@ macro_rules! impl_ser_de_js_float {
             fn de_js(value: JsValue) -> Result<$ty> {
                 match value.as_f64() {
                     Some(v) => Ok(v as $ty),
-                    None => unimplemented!()
+                    None => Err(DeJsErr::invalid_type("a number")),
                 }
             }
         }
@@ -107,7 +387,7 @@ impl DeJs for bool {
     fn de_js(value: JsValue) -> Result<Self> {
         match value.as_bool() {
             Some(v) => Ok(v),
-            None => unimplemented!(),
+            None => Err(DeJsErr::invalid_type("a boolean")),
         }
     }
 }
@@ -117,135 +397,305 @@ impl DeJs for String {
     fn de_js(value: JsValue) -> Result<Self> {
         match value.as_string() {
             Some(v) => Ok(v),
-            None => unimplemented!(),
+            None => Err(DeJsErr::invalid_type("a string")),
         }
     }
 }
 
+/// Reads the length of an array, which may be a `Proxy` with a throwing trap.
+fn array_length(array: &Array) -> Result<u32> {
+    let len = Reflect::get(array, &internal::label("length"))?;
+    Ok(len.as_f64().unwrap_or_default() as u32)
+}
+
+/// Caps the capacity preallocated for the items of an array, whose `length`
+/// can be much larger than its actual items, like with
+/// `a = []; a.length = 2 ** 32 - 1`.
+pub(crate) fn cautious_capacity(len: usize) -> usize {
+    len.min(4096)
+}
+
+/// Deserializes the items of an array, rejecting arrays with more than
+/// `capacity` items upfront.
+pub(crate) fn de_seq<T>(
+    value: JsValue,
+    capacity: usize,
+) -> Result<impl ExactSizeIterator<Item = Result<T>>>
+    where
+        T: DeJs,
+{
+    let array = value
+        .dyn_into::<Array>()
+        .map_err(|_| DeJsErr::invalid_type("an array"))?;
+    let len = array_length(&array)?;
+    if len as usize > capacity {
+        return Err(DeJsErr::invalid_length(
+            len,
+            &format!("an array of at most {} items", capacity),
+        ));
+    }
+    let own_properties_only = options().own_properties_only;
+    Ok((0..len).map(move |i| {
+        array_item(&array, i, own_properties_only)
+            .and_then(DeJs::de_js)
+            .map_err(|e| e.at_index(i))
+    }))
+}
+
 impl<T> DeJs for Vec<T>
     where
         T: DeJs,
 {
     #[inline]
     fn de_js(value: JsValue) -> Result<Vec<T>> {
-        let mut out = Vec::new();
-        let array: &Array = value.dyn_ref::<Array>().unwrap();
-        while array.length() > 0 {
-            let item = array.shift();
-            out.push(DeJs::de_js(item)?);
+        let items = de_seq(value, usize::MAX)?;
+        let mut out = Vec::with_capacity(cautious_capacity(items.len()));
+        for item in items {
+            out.push(item?);
         }
         Ok(out)
     }
 }
 
-/*macro_rules!de_json_array_impl {
-    ( $($count:expr),*) => {
+impl<T, const N: usize> DeJs for [T; N]
+    where
+        T: DeJs,
+{
+    #[inline]
+    fn de_js(value: JsValue) -> Result<[T; N]> {
+        let items = de_seq(value, N)?;
+        if items.len() != N {
+            return Err(DeJsErr::invalid_length(
+                items.len() as u32,
+                &format!("an array of {} items", N),
+            ));
+        }
+        let items = items.collect::<Result<Vec<T>>>()?;
+        Ok(items.try_into().unwrap_or_else(|_| unreachable!("length checked above")))
+    }
+}
+
+impl DeJs for () {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        if is_nullish(&value) {
+            Ok(())
+        } else {
+            Err(DeJsErr::invalid_type("null or undefined"))
+        }
+    }
+}
+
+impl<T: ?Sized> DeJs for PhantomData<T> {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Self> {
+        <()>::de_js(value).map(|()| PhantomData)
+    }
+}
+
+macro_rules! impl_de_js_tuple {
+    ( $( $len: tt => ( $( $n: tt $name: ident )+ ) )+ ) => {
         $(
-        impl<T> DeJs for [T; $count] where T: DeJs {
-            fn de_js(value: JsValue) -> Result<Self> {
-                unsafe{
-                    let mut to = std::mem::MaybeUninit::<[T; $count]>::uninit();
-                    let top: *mut T = std::mem::transmute(&mut to);
-                    de_json_array_impl_inner(top, $count, s, i)?;
-                    Ok(to.assume_init())
+            impl<$($name),+> DeJs for ($($name,)+)
+                where
+                    $($name: DeJs,)+
+            {
+                #[inline]
+                fn de_js(value: JsValue) -> Result<Self> {
+                    let arr = match value.dyn_ref::<Array>() {
+                        Some(arr) => arr,
+                        None => return Err(DeJsErr::invalid_type(concat!("a tuple of size ", $len))),
+                    };
+                    let len = array_length(arr)?;
+                    if len != $len {
+                        return Err(DeJsErr::invalid_length(len, concat!("a tuple of size ", $len)));
+                    }
+                    let own_properties_only = options().own_properties_only;
+                    Ok(($(
+                        array_item(arr, $n, own_properties_only)
+                            .and_then(DeJs::de_js)
+                            .map_err(|e| e.at_index($n))?,
+                    )+))
                 }
             }
-        }
-        )*
-    }
+        )+
+    };
 }
 
-de_json_array_impl!(
-    2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27,
-    28, 29, 30, 31, 32
-);*/
+impl_de_js_tuple! {
+    1 => (0 A)
+    2 => (0 A 1 B)
+    3 => (0 A 1 B 2 C)
+    4 => (0 A 1 B 2 C 3 D)
+    5 => (0 A 1 B 2 C 3 D 4 E)
+    6 => (0 A 1 B 2 C 3 D 4 E 5 F)
+    7 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G)
+    8 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H)
+    9 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I)
+    10 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J)
+    11 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K)
+    12 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L)
+    13 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L 12 M)
+    14 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L 12 M 13 N)
+    15 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L 12 M 13 N 14 O)
+    16 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L 12 M 13 N 14 O 15 P)
+}
 
-impl<A, B> DeJs for (A, B)
+/// Deserializes the properties of an object into map entries, in the order
+/// JavaScript enumerates them.
+pub(crate) fn de_map<K, V>(value: JsValue, mut insert: impl FnMut(K, V)) -> Result<()>
     where
-        A: DeJs,
-        B: DeJs,
+        K: DeJs,
+        V: DeJs,
 {
-    fn de_js(value: JsValue) -> Result<Self> {
-        if let Some(arr) = value.dyn_ref::<Array>() {
-            Ok((
-                DeJs::de_js(arr.get(0))?,
-                DeJs::de_js(arr.get(1))?,
-            ))
-        } else {
-            unimplemented!()
+    // Objects without a prototype aren't `instanceof Object`, so only check
+    // that the value is one.
+    if !value.is_object() {
+        return Err(DeJsErr::invalid_type("an object"));
+    }
+    let Deserializer {
+        own_properties_only,
+        reject_prototype_keys,
+        ..
+    } = options();
+    for key in object_keys(&value)?.iter() {
+        let name = key.as_string().unwrap_or_default();
+        if reject_prototype_keys && ["__proto__", "constructor", "prototype"].contains(&&*name) {
+            return Err(DeJsErr::new(format_args!("forbidden map key {:?}", name)));
         }
+        let item = if own_properties_only {
+            own_data_property(&value, &key)
+        } else {
+            Ok(Reflect::get(&value, &key)?)
+        };
+        let at_key = |e: DeJsErr| e.at_field(name.clone());
+        let v = item.and_then(DeJs::de_js).map_err(at_key)?;
+        let k = DeJs::de_js(key).map_err(at_key)?;
+        insert(k, v);
     }
+    Ok(())
 }
 
-impl<A, B, C> DeJs for (A, B, C)
+impl<K, V, S> DeJs for HashMap<K, V, S>
     where
-        A: DeJs,
-        B: DeJs,
-        C: DeJs,
+        K: DeJs + Eq + Hash,
+        V: DeJs,
+        S: BuildHasher + Default,
 {
+    #[inline]
     fn de_js(value: JsValue) -> Result<Self> {
-        if let Some(arr) = value.dyn_ref::<Array>() {
-            Ok((
-                DeJs::de_js(arr.get(0))?,
-                DeJs::de_js(arr.get(1))?,
-                DeJs::de_js(arr.get(2))?,
-            ))
-        } else {
-            unimplemented!()
-        }
+        let mut h = HashMap::with_hasher(S::default());
+        de_map(value, |k, v| {
+            h.insert(k, v);
+        })?;
+        Ok(h)
     }
 }
 
-impl<A, B, C, D> DeJs for (A, B, C, D)
+impl<T> DeJs for Box<T>
     where
-        A: DeJs,
-        B: DeJs,
-        C: DeJs,
-        D: DeJs,
+        T: DeJs,
 {
     #[inline]
+    fn de_js(value: JsValue) -> Result<Box<T>> {
+        Ok(Box::new(DeJs::de_js(value)?))
+    }
+}
+
+impl<T, E> DeJs for std::result::Result<T, E>
+    where
+        T: DeJs,
+        E: DeJs,
+{
     fn de_js(value: JsValue) -> Result<Self> {
-        if let Some(arr) = value.dyn_ref::<Array>() {
-            Ok((
-                DeJs::de_js(arr.get(0))?,
-                DeJs::de_js(arr.get(1))?,
-                DeJs::de_js(arr.get(2))?,
-                DeJs::de_js(arr.get(3))?,
-            ))
+        if !value.is_object() {
+            return Err(DeJsErr::invalid_type("a result object"));
+        }
+        if options().results_with_ok_flag {
+            match internal::obj_get(&value, "ok")?.as_bool() {
+                Some(true) => Ok(Ok(DeJs::de_js(internal::obj_get(&value, "value")?)?)),
+                Some(false) => Ok(Err(DeJs::de_js(internal::obj_get(&value, "error")?)?)),
+                None => Err(DeJsErr::invalid_type("a result object with a boolean `ok` property")),
+            }
+        } else if has_property(&value, &internal::label("Ok"))? {
+            Ok(Ok(DeJs::de_js(internal::obj_get(&value, "Ok")?)?))
+        } else if has_property(&value, &internal::label("Err"))? {
+            Ok(Err(DeJs::de_js(internal::obj_get(&value, "Err")?)?))
         } else {
-            unimplemented!()
+            Err(DeJsErr::invalid_type("a result object with an `Ok` or `Err` property"))
         }
     }
 }
 
-impl<K, V> DeJs for HashMap<K, V>
+/// `T: 'static` lets `preserve_references` share the pointers by type.
+impl<T> DeJs for Rc<T>
     where
-        K: DeJs + Eq + Hash,
-        V: DeJs,
+        T: DeJs + 'static,
+{
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Rc<T>> {
+        de_shared(value, |value| Ok(Rc::new(DeJs::de_js(value)?)))
+    }
+}
+
+/// `T: 'static` lets `preserve_references` share the pointers by type.
+impl<T> DeJs for Arc<T>
+    where
+        T: DeJs + 'static,
+{
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Arc<T>> {
+        de_shared(value, |value| Ok(Arc::new(DeJs::de_js(value)?)))
+    }
+}
+
+impl<T: ?Sized> DeJs for Cow<'_, T>
+    where
+        T: ToOwned,
+        T::Owned: DeJs,
 {
     #[inline]
     fn de_js(value: JsValue) -> Result<Self> {
-        if let Some(obj) = value.dyn_ref::<Object>() {
-            let mut h = HashMap::new();
-            for item in Object::entries(obj).iter() {
-                let item = item.dyn_ref::<Array>().unwrap();
-                let k = DeJs::de_js(item.get(0))?;
-                let v = DeJs::de_js(item.get(1))?;
-                h.insert(k, v);
-            }
-            Ok(h)
-        } else {
-            unimplemented!()
-        }
+        Ok(Cow::Owned(DeJs::de_js(value)?))
     }
 }
 
-impl<T> DeJs for Box<T>
+impl<T> DeJs for Cell<T>
     where
         T: DeJs,
 {
     #[inline]
-    fn de_js(value: JsValue) -> Result<Box<T>> {
-        Ok(Box::new(DeJs::de_js(value)?))
+    fn de_js(value: JsValue) -> Result<Cell<T>> {
+        Ok(Cell::new(DeJs::de_js(value)?))
+    }
+}
+
+impl<T> DeJs for RefCell<T>
+    where
+        T: DeJs,
+{
+    #[inline]
+    fn de_js(value: JsValue) -> Result<RefCell<T>> {
+        Ok(RefCell::new(DeJs::de_js(value)?))
+    }
+}
+
+impl<T> DeJs for Mutex<T>
+    where
+        T: DeJs,
+{
+    #[inline]
+    fn de_js(value: JsValue) -> Result<Mutex<T>> {
+        Ok(Mutex::new(DeJs::de_js(value)?))
+    }
+}
+
+impl<T> DeJs for RwLock<T>
+    where
+        T: DeJs,
+{
+    #[inline]
+    fn de_js(value: JsValue) -> Result<RwLock<T>> {
+        Ok(RwLock::new(DeJs::de_js(value)?))
     }
 }
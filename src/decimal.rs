@@ -0,0 +1,56 @@
+use wasm_bindgen::{JsCast, JsValue};
+
+use super::{DeJsErr, Result};
+
+/// Largest integer a JavaScript number holds without rounding.
+const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+/// Proxy for decimals represented by a number instead of a string, at the
+/// cost of rounding them to the nearest `f64`:
+///
+/// ```rust,ignore
+/// #[picoserde(proxy = "AsNumber<Decimal>")]
+/// pub ratio: Decimal,
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AsNumber<T>(pub T);
+
+/// Deserializes a decimal from a string, a `BigInt` or a number.
+///
+/// Numbers are rejected when they can't be trusted to be exact: integers out
+/// of the safe range, and values `to_f64` doesn't map back to the same number.
+pub(crate) fn de_decimal<T>(
+    value: JsValue,
+    parse: impl Fn(&str) -> Option<T>,
+    to_f64: impl Fn(&T) -> Option<f64>,
+) -> Result<T> {
+    let invalid = |s: &str| DeJsErr::new(format_args!("invalid decimal {:?}", s));
+    if let Some(s) = value.as_string() {
+        return parse(&s).ok_or_else(|| invalid(&s));
+    }
+    if let Some(int) = value.dyn_ref::<js_sys::BigInt>() {
+        let s = String::from(int.to_string(10).map_err(JsValue::from)?);
+        return parse(&s).ok_or_else(|| invalid(&s));
+    }
+    let number = match value.as_f64() {
+        Some(number) if number.is_finite() => number,
+        Some(number) => return Err(invalid(&number.to_string())),
+        None => return Err(DeJsErr::invalid_type("a decimal string, number or BigInt")),
+    };
+    if number.fract() == 0.0 && number.abs() > MAX_SAFE_INTEGER {
+        return Err(DeJsErr::new(format_args!(
+            "{} is out of the safe integer range and may have lost precision, expected a string or BigInt",
+            number
+        )));
+    }
+    // `Display` of an `f64` is the shortest string parsing back to it.
+    let s = number.to_string();
+    match parse(&s) {
+        Some(decimal) if to_f64(&decimal) == Some(number) => Ok(decimal),
+        Some(_) => Err(DeJsErr::new(format_args!(
+            "{} can't be represented exactly as a decimal",
+            number
+        ))),
+        None => Err(invalid(&s)),
+    }
+}
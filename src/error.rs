@@ -1,3 +1,5 @@
+use std::fmt;
+
 use wasm_bindgen::prelude::*;
 
 /// A newtype that represents Serde errors as JavaScript exceptions.
@@ -5,26 +7,114 @@ use wasm_bindgen::prelude::*;
 pub struct Error(JsValue);
 
 #[derive(Debug)]
-pub struct DeJsErr(JsValue);
-
-impl std::fmt::Display for DeJsErr {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        #[wasm_bindgen]
-        extern "C" {
-            #[wasm_bindgen(js_name = String)]
-            pub fn to_string(value: &JsValue) -> String;
-        }
+pub struct DeJsErr {
+    kind: DeJsErrKind,
+    /// Path to the value that caused the error, innermost segment first.
+    path: Vec<PathSegment>,
+}
+
+/// The reason of a [`DeJsErr`].
+#[derive(Debug)]
+pub enum DeJsErrKind {
+    /// A value of an unexpected type, with a description of the expected one.
+    InvalidType(String),
+    /// An array of an unexpected length, with a description of the expected one.
+    InvalidLength { len: u32, expected: String },
+    /// An exception thrown on the JavaScript side.
+    JsException(JsValue),
+    /// Any other error.
+    Custom(String),
+}
 
-        to_string(&self.0).fmt(f)
+#[derive(Debug)]
+enum PathSegment {
+    Field(String),
+    Index(u32),
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = String)]
+    fn to_string(value: &JsValue) -> String;
+}
+
+impl fmt::Display for DeJsErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            DeJsErrKind::InvalidType(expected) => write!(f, "invalid type, expected {}", expected)?,
+            DeJsErrKind::InvalidLength { len, expected } => {
+                write!(f, "invalid length {}, expected {}", len, expected)?
+            }
+            DeJsErrKind::JsException(error) => to_string(error).fmt(f)?,
+            DeJsErrKind::Custom(msg) => msg.fmt(f)?,
+        }
+        if !self.path.is_empty() {
+            write!(f, " at {}", self.path())?;
+        }
+        Ok(())
     }
 }
 
 impl std::error::Error for DeJsErr {}
 
 impl DeJsErr {
-    /// Creates a JavaScript `Error` with a given message.
-    pub fn new<T: std::fmt::Display>(msg: T) -> Self {
-        DeJsErr(js_sys::Error::new(&msg.to_string()).into())
+    fn from_kind(kind: DeJsErrKind) -> Self {
+        DeJsErr {
+            kind,
+            path: Vec::new(),
+        }
+    }
+
+    /// Creates an error with a given message.
+    pub fn new<T: fmt::Display>(msg: T) -> Self {
+        Self::from_kind(DeJsErrKind::Custom(msg.to_string()))
+    }
+
+    /// Creates an error for a JavaScript value of an unexpected type.
+    pub fn invalid_type(expected: &str) -> Self {
+        Self::from_kind(DeJsErrKind::InvalidType(expected.to_string()))
+    }
+
+    /// Creates an error for a JavaScript array of an unexpected length.
+    pub fn invalid_length(len: u32, expected: &str) -> Self {
+        Self::from_kind(DeJsErrKind::InvalidLength {
+            len,
+            expected: expected.to_string(),
+        })
+    }
+
+    /// Returns the reason of the error.
+    pub fn kind(&self) -> &DeJsErrKind {
+        &self.kind
+    }
+
+    /// Returns the path to the value that caused the error, like
+    /// `items[2].name`, or an empty string for the root value.
+    pub fn path(&self) -> String {
+        let mut path = String::new();
+        for segment in self.path.iter().rev() {
+            match segment {
+                PathSegment::Field(name) if path.is_empty() => path.push_str(name),
+                PathSegment::Field(name) => {
+                    path.push('.');
+                    path.push_str(name);
+                }
+                PathSegment::Index(index) => path.push_str(&format!("[{}]", index)),
+            }
+        }
+        path
+    }
+
+    /// Marks the error as coming from the field `name` of an object.
+    pub fn at_field<T: Into<String>>(mut self, name: T) -> Self {
+        self.path.push(PathSegment::Field(name.into()));
+        self
+    }
+
+    /// Marks the error as coming from the element `index` of an array.
+    pub fn at_index(mut self, index: u32) -> Self {
+        self.path.push(PathSegment::Index(index));
+        self
     }
 }
 
@@ -32,7 +122,7 @@ impl DeJsErr {
 /// imports that return JavaScript exceptions as `Result<T, JsValue>`.
 impl From<JsValue> for DeJsErr {
     fn from(error: JsValue) -> DeJsErr {
-        DeJsErr(error)
+        DeJsErr::from_kind(DeJsErrKind::JsException(error))
     }
 }
 
@@ -40,6 +130,16 @@ impl From<JsValue> for DeJsErr {
 // that return `Result<T, JsValue>` to throw JavaScript exceptions.
 impl From<DeJsErr> for JsValue {
     fn from(error: DeJsErr) -> JsValue {
-        error.0
+        match &error.kind {
+            // Rethrow JavaScript exceptions as they are when there is nothing
+            // to add to them, and as the cause of an error with the path otherwise.
+            DeJsErrKind::JsException(exception) if error.path.is_empty() => exception.clone(),
+            DeJsErrKind::JsException(exception) => {
+                let js_error = js_sys::Error::new(&error.to_string());
+                js_error.set_cause(exception);
+                js_error.into()
+            }
+            _ => js_sys::Error::new(&error.to_string()).into(),
+        }
     }
 }
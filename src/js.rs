@@ -0,0 +1,52 @@
+use js_sys::{
+    Array, ArrayBuffer, BigInt, BigInt64Array, BigUint64Array, DataView, Date, Error,
+    Float32Array, Float64Array, Function, Int16Array, Int32Array, Int8Array, JsString, Map,
+    Object, Promise, RegExp, Set, Symbol, Uint16Array, Uint32Array, Uint8Array,
+    Uint8ClampedArray, WeakMap, WeakSet,
+};
+use wasm_bindgen::{JsCast, JsValue};
+
+use super::{DeJs, DeJsErr, Result, SerJs};
+
+/// JavaScript values are passed through as they are.
+impl SerJs for JsValue {
+    fn ser_js(&self) -> JsValue {
+        self.clone()
+    }
+}
+
+impl DeJs for JsValue {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<JsValue> {
+        Ok(value)
+    }
+}
+
+/// `js_sys` types are passed through too, after checking the type of the
+/// value on deserialization.
+macro_rules! impl_ser_de_js_js_sys {
+    ( $ ( $ ty: ident ) * ) => {
+        $(
+            impl SerJs for $ty {
+                fn ser_js(&self) -> JsValue {
+                    self.into()
+                }
+            }
+
+            impl DeJs for $ty {
+                #[inline]
+                fn de_js(value: JsValue) -> Result<$ty> {
+                    value
+                        .dyn_into()
+                        .map_err(|_| DeJsErr::invalid_type(concat!("a JavaScript ", stringify!($ty))))
+                }
+            }
+        )*
+    };
+}
+
+impl_ser_de_js_js_sys!(
+    Object Array Function Map Set WeakMap WeakSet Promise Date RegExp Error Symbol BigInt JsString
+    ArrayBuffer DataView Int8Array Uint8Array Uint8ClampedArray Int16Array Uint16Array Int32Array
+    Uint32Array Float32Array Float64Array BigInt64Array BigUint64Array
+);
@@ -4,15 +4,34 @@
 
 use wasm_bindgen::prelude::*;
 
-pub use de::DeJs;
-pub use de::internal;
-pub use error::DeJsErr;
+pub use bytes::ByteArray;
+#[cfg(any(feature = "rust_decimal", feature = "bigdecimal"))]
+pub use decimal::AsNumber;
+pub use de::{DeJs, Deserializer};
+pub use date::{EpochMillis, Iso8601, JsDate};
+pub use error::{DeJsErr, DeJsErrKind};
 pub use picoserde_derive_wasm_bindgen::{DeJs, SerJs};
-pub use ser::SerJs;
+pub use ser::{MyCustomJsObject, SerJs, Serializer, register_js_class, ser_error};
+pub use value::Value;
 
+mod bytes;
+mod compat;
+mod date;
+#[cfg(any(feature = "rust_decimal", feature = "bigdecimal"))]
+mod decimal;
 mod de;
 mod error;
+mod js;
+mod net;
+mod path;
 mod ser;
+mod value;
+
+/// Helpers for the code generated by the derive macros.
+pub mod internal {
+    pub use crate::de::internal::*;
+    pub use crate::ser::ser_frozen;
+}
 
 type Result<T> = std::result::Result<T, DeJsErr>;
 
@@ -31,10 +50,10 @@ fn static_str_to_js(s: &'static str) -> JsValue {
 
 /// Converts [`JsValue`] into a Rust type.
 pub fn from_value<T: DeJs>(value: JsValue) -> Result<T> {
-    DeJs::de_js(value)
+    Deserializer::new().deserialize(value)
 }
 
 /// Converts a Rust value into a [`JsValue`].
-pub fn to_value<T: SerJs>(value: &T) -> Result<JsValue> {
-    Ok(SerJs::serialize_js(value))
+pub fn to_value<T: SerJs + ?Sized>(value: &T) -> Result<JsValue> {
+    Serializer::new().serialize(value)
 }
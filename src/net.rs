@@ -0,0 +1,35 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use wasm_bindgen::JsValue;
+
+use super::{DeJs, DeJsErr, Result, SerJs};
+
+/// Addresses are represented by their canonical string form.
+macro_rules! impl_ser_de_js_addr {
+    ( $ ty: ident, $ expected: expr) => {
+        impl SerJs for $ty {
+            fn ser_js(&self) -> JsValue {
+                JsValue::from_str(&self.to_string())
+            }
+        }
+
+        impl DeJs for $ty {
+            #[inline]
+            fn de_js(value: JsValue) -> Result<$ty> {
+                match value.as_string() {
+                    Some(s) => s.parse().map_err(|_| {
+                        DeJsErr::new(format_args!("invalid {} {:?}", $expected, s))
+                    }),
+                    None => Err(DeJsErr::invalid_type(concat!("a string of ", $expected))),
+                }
+            }
+        }
+    };
+}
+
+impl_ser_de_js_addr!(IpAddr, "IP address");
+impl_ser_de_js_addr!(Ipv4Addr, "IPv4 address");
+impl_ser_de_js_addr!(Ipv6Addr, "IPv6 address");
+impl_ser_de_js_addr!(SocketAddr, "socket address");
+impl_ser_de_js_addr!(SocketAddrV4, "IPv4 socket address");
+impl_ser_de_js_addr!(SocketAddrV6, "IPv6 socket address");
@@ -0,0 +1,60 @@
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+use js_sys::JsString;
+use wasm_bindgen::{JsCast, JsValue};
+
+use super::{DeJs, DeJsErr, Result, SerJs, ser_error};
+
+/// Paths and OS strings are represented by a string, which fails for the
+/// ones that aren't valid UTF-8.
+impl SerJs for OsStr {
+    fn ser_js(&self) -> JsValue {
+        match self.to_str() {
+            Some(s) => s.ser_js(),
+            None => ser_error(DeJsErr::new(format_args!("{:?} is not valid UTF-8", self))),
+        }
+    }
+}
+
+impl SerJs for OsString {
+    fn ser_js(&self) -> JsValue {
+        self.as_os_str().ser_js()
+    }
+}
+
+/// Deserializes a string, rejecting lone surrogates that would otherwise be
+/// replaced silently on the way to UTF-8.
+fn de_utf8(value: JsValue) -> Result<String> {
+    match value.dyn_ref::<JsString>() {
+        Some(s) if s.is_valid_utf16() => Ok(s.into()),
+        Some(_) => Err(DeJsErr::new("path is not valid UTF-8")),
+        None => Err(DeJsErr::invalid_type("a path string")),
+    }
+}
+
+impl DeJs for OsString {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<OsString> {
+        Ok(de_utf8(value)?.into())
+    }
+}
+
+impl SerJs for Path {
+    fn ser_js(&self) -> JsValue {
+        self.as_os_str().ser_js()
+    }
+}
+
+impl SerJs for PathBuf {
+    fn ser_js(&self) -> JsValue {
+        self.as_path().ser_js()
+    }
+}
+
+impl DeJs for PathBuf {
+    #[inline]
+    fn de_js(value: JsValue) -> Result<PathBuf> {
+        Ok(de_utf8(value)?.into())
+    }
+}
@@ -1,9 +1,15 @@
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, RwLock, TryLockError};
 
-use js_sys::{Array, JsString};
+use fnv::{FnvHashMap, FnvHashSet};
+use js_sys::{Array, Function, Object, Reflect, Set, WeakSet};
 use wasm_bindgen::prelude::*;
 
-use super::{ static_str_to_js};
+use super::{DeJsErr, Result, static_str_to_js};
 
 //type Result<T = JsValue> = super::Result<T>;
 
@@ -17,6 +23,9 @@ mod internal {
         #[wasm_bindgen(constructor)]
         pub fn new() -> Object;
 
+        #[wasm_bindgen(static_method_of = Object)]
+        pub fn create(prototype: &JsValue) -> Object;
+
         #[wasm_bindgen(method, indexing_setter)]
         pub fn set(this: &Object, key: JsValue, value: JsValue);
     }
@@ -24,6 +33,269 @@ mod internal {
 
 pub type MyCustomJsObject = internal::Object;
 
+impl MyCustomJsObject {
+    /// Creates an object without a prototype, where keys like `__proto__`
+    /// are plain properties.
+    pub fn with_null_prototype() -> MyCustomJsObject {
+        track(MyCustomJsObject::create(&JsValue::NULL))
+    }
+
+    /// Creates an object inheriting from `prototype`, for `instanceof` checks
+    /// and methods.
+    pub fn with_prototype(prototype: &JsValue) -> MyCustomJsObject {
+        track(MyCustomJsObject::create(prototype))
+    }
+
+    /// Creates an instance of the JavaScript class `name`, registered with
+    /// [`register_js_class`] or else global, without calling its constructor.
+    ///
+    /// A missing class is reported as a serialization error.
+    pub fn with_js_class(name: &str) -> MyCustomJsObject {
+        let constructor = JS_CLASSES
+            .with(|classes| classes.borrow().get(name).cloned())
+            .or_else(|| Reflect::get(&js_sys::global(), &JsValue::from_str(name)).ok())
+            .filter(JsValue::is_function);
+        let prototype = constructor
+            .and_then(|constructor| Reflect::get(&constructor, &static_str_to_js("prototype")).ok())
+            .filter(JsValue::is_object);
+        match prototype {
+            Some(prototype) => MyCustomJsObject::with_prototype(&prototype),
+            None => {
+                ser_error(DeJsErr::new(format_args!("JavaScript class {:?} not found", name)));
+                track(MyCustomJsObject::new())
+            }
+        }
+    }
+}
+
+thread_local! {
+    static JS_CLASSES: RefCell<FnvHashMap<String, JsValue>> = Default::default();
+}
+
+/// Registers the class used by `#[picoserde(js_class = "name")]`, for classes
+/// that aren't available as globals, like the ones of ES modules.
+pub fn register_js_class(name: &str, constructor: &Function) {
+    JS_CLASSES.with(|classes| {
+        classes
+            .borrow_mut()
+            .insert(name.to_string(), constructor.clone().into())
+    });
+}
+
+/// Serializer options, for when the defaults of [`to_value`](crate::to_value)
+/// don't fit.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Serializer {
+    preserve_references: bool,
+    results_with_ok_flag: bool,
+    none_as_null: bool,
+    null_prototype_structs: bool,
+    freeze: bool,
+    deep_freeze: bool,
+}
+
+impl Serializer {
+    /// Creates a serializer with the default options.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Serializes `Rc`s and `Arc`s pointing to the same value into the same
+    /// JavaScript object instead of separate copies.
+    pub fn preserve_references(mut self, value: bool) -> Self {
+        self.preserve_references = value;
+        self
+    }
+
+    /// Serializes `Result`s as `{ ok: true, value }` and `{ ok: false, error }`
+    /// instead of `{ Ok: value }` and `{ Err: error }`.
+    pub fn results_with_ok_flag(mut self, value: bool) -> Self {
+        self.results_with_ok_flag = value;
+        self
+    }
+
+    /// Serializes `None` as `null` instead of `undefined`.
+    pub fn none_as_null(mut self, value: bool) -> Self {
+        self.none_as_null = value;
+        self
+    }
+
+    /// Serializes structs into objects without a prototype, like maps always
+    /// are, instead of plain objects.
+    pub fn null_prototype_structs(mut self, value: bool) -> Self {
+        self.null_prototype_structs = value;
+        self
+    }
+
+    /// Freezes the resulting object or array with `Object.freeze`, so that
+    /// mutating it throws in strict mode.
+    pub fn freeze(mut self, value: bool) -> Self {
+        self.freeze = value;
+        self
+    }
+
+    /// Freezes the resulting object or array and the objects and arrays it
+    /// contains, leaving the `JsValue`s passed through as they are.
+    pub fn deep_freeze(mut self, value: bool) -> Self {
+        self.deep_freeze = value;
+        self
+    }
+
+    /// Converts a Rust value into a [`JsValue`] using these options.
+    pub fn serialize<T: SerJs + ?Sized>(&self, value: &T) -> Result<JsValue> {
+        // Keep the state of an outer serialization if this one is nested in it.
+        let mut outer = StateGuard(Some(STATE.with(|state| state.replace(State::new(*self)))));
+        let js_value = if self.freeze || self.deep_freeze {
+            ser_frozen(self.deep_freeze, || value.serialize_js())
+        } else {
+            value.serialize_js()
+        };
+        let outer = outer.0.take().unwrap_or_default();
+        match STATE.with(|state| state.replace(outer)).error {
+            Some(err) => Err(err),
+            None => Ok(js_value),
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    options: Serializer,
+    error: Option<DeJsErr>,
+    /// Shared pointers currently being serialized, to detect cycles.
+    in_progress: FnvHashSet<*const u8>,
+    /// Already serialized shared pointers, with `preserve_references`.
+    shared: FnvHashMap<*const u8, JsValue>,
+    /// Objects and arrays created by the serializer, while freezing.
+    created: Option<WeakSet>,
+}
+
+impl State {
+    fn new(options: Serializer) -> Self {
+        State {
+            options,
+            ..Default::default()
+        }
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<State> = Default::default();
+}
+
+/// Restores the state of an outer serialization if this one panics.
+struct StateGuard(Option<State>);
+
+impl Drop for StateGuard {
+    fn drop(&mut self) {
+        if let Some(outer) = self.0.take() {
+            STATE.with(|state| state.replace(outer));
+        }
+    }
+}
+
+fn options() -> Serializer {
+    STATE.with(|state| state.borrow().options)
+}
+
+/// Reports a value that can't be serialized.
+///
+/// [`SerJs::ser_js`] is infallible, so implementations report failures here
+/// and return the result of this function, `undefined`, as a placeholder.
+/// [`to_value`](crate::to_value) then returns the first reported error.
+///
+/// Errors reported outside of [`to_value`](crate::to_value) or
+/// [`Serializer::serialize`], like when calling [`SerJs::ser_js`] directly,
+/// are silently dropped, so serialize through them to get the errors.
+pub fn ser_error(err: DeJsErr) -> JsValue {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        if state.error.is_none() {
+            state.error = Some(err);
+        }
+    });
+    JsValue::UNDEFINED
+}
+
+/// Records an object or array created by the serializer, as only these get
+/// frozen, unlike the `JsValue`s passed through which belong to the caller.
+fn track<T: AsRef<JsValue>>(value: T) -> T {
+    STATE.with(|state| {
+        if let Some(created) = &state.borrow().created {
+            created.add(value.as_ref().unchecked_ref());
+        }
+    });
+    value
+}
+
+/// Serializes a value with `ser` and freezes the result, and with `deep` the
+/// objects and arrays the serializer created in it.
+///
+/// Used by `#[picoserde(freeze)]` and `#[picoserde(freeze = "deep")]`.
+pub fn ser_frozen(deep: bool, ser: impl FnOnce() -> JsValue) -> JsValue {
+    // Nested frozen values share the objects tracked by the outer one.
+    let (outer, created) = STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let outer = state.created.clone();
+        let created = outer.clone().unwrap_or_else(WeakSet::new);
+        state.created = Some(created.clone());
+        (outer, created)
+    });
+    let value = ser();
+    STATE.with(|state| state.borrow_mut().created = outer);
+    freeze(&created, &Set::new(&JsValue::UNDEFINED), &value, deep);
+    value
+}
+
+/// Freezes `value` if the serializer created it, and with `deep` the values
+/// it contains, visiting each object once.
+fn freeze(created: &WeakSet, visited: &Set, value: &JsValue, deep: bool) {
+    if !value.is_object() || visited.has(value) {
+        return;
+    }
+    visited.add(value);
+    let object = value.unchecked_ref::<Object>();
+    if !created.has(object) {
+        return;
+    }
+    Object::freeze(object);
+    if deep {
+        for item in Object::values(object).iter() {
+            freeze(created, visited, &item, true);
+        }
+    }
+}
+
+/// Serializes a value behind a shared pointer, detecting cycles and, if
+/// enabled, reusing the JavaScript object of a pointer seen before.
+fn ser_shared<T: SerJs + ?Sized>(ptr: *const u8, value: &T) -> JsValue {
+    let cached = STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        if let Some(js_value) = state.shared.get(&ptr) {
+            Some(Ok(js_value.clone()))
+        } else if !state.in_progress.insert(ptr) {
+            Some(Err(DeJsErr::new("cycle detected in shared references")))
+        } else {
+            None
+        }
+    });
+    match cached {
+        Some(Ok(js_value)) => return js_value,
+        Some(Err(err)) => return ser_error(err),
+        None => {}
+    }
+
+    let js_value = value.ser_js();
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.in_progress.remove(&ptr);
+        if state.options.preserve_references {
+            state.shared.insert(ptr, js_value.clone());
+        }
+    });
+    js_value
+}
+
 pub trait SerJs {
     fn serialize_js(&self) -> JsValue {
         self.ser_js()
@@ -34,7 +306,11 @@ pub trait SerJs {
     }
 
     fn ser_object(&self) -> MyCustomJsObject {
-        MyCustomJsObject::new()
+        if options().null_prototype_structs {
+            MyCustomJsObject::with_null_prototype()
+        } else {
+            track(MyCustomJsObject::new())
+        }
     }
 
     fn ser_js(&self) -> JsValue;
@@ -94,6 +370,8 @@ impl<T> SerJs for Option<T>
     fn ser_js(&self) -> JsValue {
         if let Some(v) = self {
             v.ser_js()
+        } else if options().none_as_null {
+            JsValue::NULL
         } else {
             JsValue::UNDEFINED
         }
@@ -109,9 +387,15 @@ impl SerJs for bool {
     }
 }
 
+impl SerJs for str {
+    fn ser_js(&self) -> JsValue {
+        JsValue::from_str(self)
+    }
+}
+
 impl SerJs for String {
     fn ser_js(&self) -> JsValue {
-        JsString::from((*self).clone()).into()
+        self.as_str().ser_js()
     }
 }
 
@@ -120,12 +404,22 @@ impl<T> SerJs for Vec<T>
         T: SerJs,
 {
     fn ser_js(&self) -> JsValue {
-        let array = Array::new();
-        for item in self {
-            array.push(&item.ser_js());
-        }
-        array.into()
+        self.as_slice().ser_js()
+    }
+}
+
+/// Serializes a sequence of items into an array, in iteration order.
+pub(crate) fn ser_seq<'a, T>(items: impl ExactSizeIterator<Item = &'a T>) -> JsValue
+    where
+        T: SerJs + 'a,
+{
+    // Allocating with the final length upfront avoids growing the array
+    // on every element, which `push` does for each boundary crossing.
+    let array = track(Array::new_with_length(items.len() as u32));
+    for (i, item) in items.enumerate() {
+        array.set(i as u32, item.ser_js());
     }
+    array.into()
 }
 
 impl<T> SerJs for [T]
@@ -133,78 +427,221 @@ impl<T> SerJs for [T]
         T: SerJs,
 {
     fn ser_js(&self) -> JsValue {
-        let array = Array::new();
-        for item in self {
-            array.push(&item.ser_js());
-        }
-        array.into()
+        ser_seq(self.iter())
     }
 }
 
-impl<A, B> SerJs for (A, B)
+impl<T, const N: usize> SerJs for [T; N]
     where
-        A: SerJs,
-        B: SerJs,
+        T: SerJs,
 {
     fn ser_js(&self) -> JsValue {
-        let array = Array::new();
-        array.push(&self.0.ser_js());
-        array.push(&self.1.ser_js());
-        array.into()
+        ser_seq(self.iter())
     }
 }
 
-impl<A, B, C> SerJs for (A, B, C)
-    where
-        A: SerJs,
-        B: SerJs,
-        C: SerJs,
-{
+impl SerJs for () {
+    fn ser_js(&self) -> JsValue {
+        JsValue::UNDEFINED
+    }
+}
+
+impl<T: ?Sized> SerJs for PhantomData<T> {
     fn ser_js(&self) -> JsValue {
-        let array = Array::new();
-        array.push(&self.0.ser_js());
-        array.push(&self.1.ser_js());
-        array.push(&self.2.ser_js());
-        array.into()
+        JsValue::UNDEFINED
     }
 }
 
-impl<A, B, C, D> SerJs for (A, B, C, D)
+macro_rules! impl_ser_js_tuple {
+    ( $( $len: tt => ( $( $n: tt $name: ident )+ ) )+ ) => {
+        $(
+            impl<$($name),+> SerJs for ($($name,)+)
+                where
+                    $($name: SerJs,)+
+            {
+                fn ser_js(&self) -> JsValue {
+                    let array = track(Array::new_with_length($len));
+                    $(array.set($n, self.$n.ser_js());)+
+                    array.into()
+                }
+            }
+        )+
+    };
+}
+
+impl_ser_js_tuple! {
+    1 => (0 A)
+    2 => (0 A 1 B)
+    3 => (0 A 1 B 2 C)
+    4 => (0 A 1 B 2 C 3 D)
+    5 => (0 A 1 B 2 C 3 D 4 E)
+    6 => (0 A 1 B 2 C 3 D 4 E 5 F)
+    7 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G)
+    8 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H)
+    9 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I)
+    10 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J)
+    11 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K)
+    12 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L)
+    13 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L 12 M)
+    14 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L 12 M 13 N)
+    15 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L 12 M 13 N 14 O)
+    16 => (0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L 12 M 13 N 14 O 15 P)
+}
+
+/// Serializes the entries of a map into the properties of an object, in
+/// iteration order.
+///
+/// The object has no prototype, so that no key collides with the properties
+/// of `Object.prototype`, or sets the prototype in the case of `__proto__`.
+pub(crate) fn ser_map<'a, K, V>(entries: impl IntoIterator<Item = (&'a K, &'a V)>) -> JsValue
     where
-        A: SerJs,
-        B: SerJs,
-        C: SerJs,
-        D: SerJs,
+        K: SerJs + 'a,
+        V: SerJs + 'a,
 {
-    fn ser_js(&self) -> JsValue {
-        let array = Array::new();
-        array.push(&self.0.ser_js());
-        array.push(&self.1.ser_js());
-        array.push(&self.2.ser_js());
-        array.push(&self.3.ser_js());
-        array.into()
+    let object = MyCustomJsObject::with_null_prototype();
+    for (k, v) in entries {
+        object.set(k.ser_js(), v.ser_js());
     }
+    object.into()
 }
 
-impl<K, V> SerJs for HashMap<K, V>
+impl<K, V, S> SerJs for HashMap<K, V, S>
     where
         K: SerJs,
         V: SerJs,
+{
+    fn ser_js(&self) -> JsValue {
+        ser_map(self)
+    }
+}
+
+impl<T, E> SerJs for std::result::Result<T, E>
+    where
+        T: SerJs,
+        E: SerJs,
 {
     fn ser_js(&self) -> JsValue {
         let object = self.ser_object();
-        for (k, v) in self {
-            object.set(k.ser_js(), v.ser_js());
+        if options().results_with_ok_flag {
+            match self {
+                Ok(value) => {
+                    object.set(self.label("ok"), JsValue::TRUE);
+                    object.set(self.label("value"), value.ser_js());
+                }
+                Err(error) => {
+                    object.set(self.label("ok"), JsValue::FALSE);
+                    object.set(self.label("error"), error.ser_js());
+                }
+            }
+        } else {
+            match self {
+                Ok(value) => object.set(self.label("Ok"), value.ser_js()),
+                Err(error) => object.set(self.label("Err"), error.ser_js()),
+            }
         }
         object.into()
     }
 }
 
-impl<T> SerJs for Box<T>
+impl<T: ?Sized> SerJs for &T
+    where
+        T: SerJs,
+{
+    fn ser_js(&self) -> JsValue {
+        (**self).ser_js()
+    }
+}
+
+impl<T: ?Sized> SerJs for &mut T
+    where
+        T: SerJs,
+{
+    fn ser_js(&self) -> JsValue {
+        (**self).ser_js()
+    }
+}
+
+impl<T: ?Sized> SerJs for Box<T>
+    where
+        T: SerJs,
+{
+    fn ser_js(&self) -> JsValue {
+        (**self).ser_js()
+    }
+}
+
+impl<T: ?Sized> SerJs for Rc<T>
     where
         T: SerJs,
+{
+    fn ser_js(&self) -> JsValue {
+        ser_shared(Rc::as_ptr(self) as *const u8, &**self)
+    }
+}
+
+impl<T: ?Sized> SerJs for Arc<T>
+    where
+        T: SerJs,
+{
+    fn ser_js(&self) -> JsValue {
+        ser_shared(Arc::as_ptr(self) as *const u8, &**self)
+    }
+}
+
+impl<T: ?Sized> SerJs for Cow<'_, T>
+    where
+        T: SerJs + ToOwned,
 {
     fn ser_js(&self) -> JsValue {
         (**self).ser_js()
     }
 }
+
+impl<T> SerJs for Cell<T>
+    where
+        T: SerJs + Copy,
+{
+    fn ser_js(&self) -> JsValue {
+        self.get().ser_js()
+    }
+}
+
+impl<T: ?Sized> SerJs for RefCell<T>
+    where
+        T: SerJs,
+{
+    fn ser_js(&self) -> JsValue {
+        match self.try_borrow() {
+            Ok(value) => value.ser_js(),
+            Err(_) => ser_error(DeJsErr::new("RefCell is already mutably borrowed")),
+        }
+    }
+}
+
+impl<T: ?Sized> SerJs for Mutex<T>
+    where
+        T: SerJs,
+{
+    fn ser_js(&self) -> JsValue {
+        match self.try_lock() {
+            Ok(value) => value.ser_js(),
+            Err(TryLockError::WouldBlock) => ser_error(DeJsErr::new("Mutex is already locked")),
+            Err(TryLockError::Poisoned(_)) => ser_error(DeJsErr::new("Mutex is poisoned")),
+        }
+    }
+}
+
+impl<T: ?Sized> SerJs for RwLock<T>
+    where
+        T: SerJs,
+{
+    fn ser_js(&self) -> JsValue {
+        match self.try_read() {
+            Ok(value) => value.ser_js(),
+            Err(TryLockError::WouldBlock) => {
+                ser_error(DeJsErr::new("RwLock is already locked for writing"))
+            }
+            Err(TryLockError::Poisoned(_)) => ser_error(DeJsErr::new("RwLock is poisoned")),
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::ops::Index;
+
+use js_sys::{Array, Object, Reflect, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+
+use super::bytes::ser_bytes;
+use super::de::{de_map, de_seq};
+use super::ser::{ser_map, ser_seq};
+use super::{DeJs, DeJsErr, Result, SerJs, ser_error};
+
+/// Any JavaScript value that survives a copy into Rust, for the parts of a
+/// payload without a schema.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Value {
+    #[default]
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    /// A `BigInt`, as a string of its decimal digits.
+    BigInt(String),
+    String(String),
+    Array(Vec<Value>),
+    /// A plain object, with its properties in enumeration order.
+    Object(Vec<(String, Value)>),
+    /// A `Uint8Array`.
+    Bytes(Vec<u8>),
+}
+
+static UNDEFINED: Value = Value::Undefined;
+
+impl Value {
+    /// Returns the property `key` of an object, or `None` for other values
+    /// and missing properties.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Returns the item `index` of an array, or `None` for other values and
+    /// out of bounds indices.
+    pub fn get_index(&self, index: usize) -> Option<&Value> {
+        match self {
+            Value::Array(items) => items.get(index),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the value is `undefined`.
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, Value::Undefined)
+    }
+
+    /// Returns whether the value is `null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Returns the boolean, if the value is one.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the number, if the value is one.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns the string, if the value is one.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the items, if the value is an array.
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the properties, if the value is an object.
+    pub fn as_object(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Object(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes, if the value is a `Uint8Array`.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+}
+
+/// Indexing an object by a missing key, or anything else, gives `Undefined`
+/// like in JavaScript.
+impl Index<&str> for Value {
+    type Output = Value;
+
+    fn index(&self, key: &str) -> &Value {
+        self.get(key).unwrap_or(&UNDEFINED)
+    }
+}
+
+/// Indexing an array out of bounds, or anything else, gives `Undefined`
+/// like in JavaScript.
+impl Index<usize> for Value {
+    type Output = Value;
+
+    fn index(&self, index: usize) -> &Value {
+        self.get_index(index).unwrap_or(&UNDEFINED)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(n: f64) -> Self {
+        Value::Number(n)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(items: Vec<Value>) -> Self {
+        Value::Array(items)
+    }
+}
+
+impl SerJs for Value {
+    fn ser_js(&self) -> JsValue {
+        match self {
+            Value::Undefined => JsValue::UNDEFINED,
+            Value::Null => JsValue::NULL,
+            Value::Bool(b) => b.ser_js(),
+            Value::Number(n) => JsValue::from_f64(*n),
+            Value::BigInt(digits) => match js_sys::BigInt::new(&JsValue::from_str(digits)) {
+                Ok(int) => int.into(),
+                Err(_) => ser_error(DeJsErr::new(format_args!("invalid BigInt {:?}", digits))),
+            },
+            Value::String(s) => s.ser_js(),
+            Value::Array(items) => ser_seq(items.iter()),
+            Value::Object(entries) => ser_map(entries.iter().map(|(k, v)| (k, v))),
+            Value::Bytes(bytes) => ser_bytes(bytes),
+        }
+    }
+}
+
+/// Whether an object inherits from `Object.prototype` or from nothing.
+fn is_plain_object(value: &JsValue) -> Result<bool> {
+    let prototype = Reflect::get_prototype_of(value)?;
+    Ok(prototype.is_null() || Object::get_prototype_of(&Object::new()) == prototype)
+}
+
+impl DeJs for Value {
+    fn de_js(value: JsValue) -> Result<Value> {
+        if value.is_undefined() {
+            return Ok(Value::Undefined);
+        }
+        if value.is_null() {
+            return Ok(Value::Null);
+        }
+        if let Some(b) = value.as_bool() {
+            return Ok(Value::Bool(b));
+        }
+        if let Some(n) = value.as_f64() {
+            return Ok(Value::Number(n));
+        }
+        if let Some(s) = value.as_string() {
+            return Ok(Value::String(s));
+        }
+        if let Some(int) = value.dyn_ref::<js_sys::BigInt>() {
+            let digits = int.to_string(10).map_err(JsValue::from)?;
+            return Ok(Value::BigInt(digits.into()));
+        }
+        if Array::is_array(&value) {
+            return Ok(Value::Array(de_seq(value, usize::MAX)?.collect::<Result<_>>()?));
+        }
+        if let Some(bytes) = value.dyn_ref::<Uint8Array>() {
+            return Ok(Value::Bytes(bytes.to_vec()));
+        }
+        // Only plain objects, as the data of a `Date`, `Map` and the like isn't
+        // in their properties.
+        if !value.is_object() || !is_plain_object(&value)? {
+            return Err(DeJsErr::invalid_type("a value with a Rust counterpart"));
+        }
+        let mut entries = Vec::new();
+        de_map(value, |k, v| entries.push((k, v)))?;
+        Ok(Value::Object(entries))
+    }
+}
@@ -0,0 +1,1168 @@
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use picoserde_wasm_bindgen::{
+    from_value, to_value, ByteArray, DeJs, DeJsErrKind, Deserializer, EpochMillis, JsDate, SerJs,
+    Serializer,
+};
+use js_sys::Reflect;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::*;
+
+fn test<T>(value: T, json: &str)
+where
+    T: SerJs + DeJs + PartialEq + Debug,
+{
+    let js_value = to_value(&value).unwrap();
+    assert_eq!(
+        js_sys::JSON::stringify(&js_value).unwrap(),
+        json,
+        "to_value from {:?}",
+        value
+    );
+    let restored: T = from_value(js_value).unwrap();
+    assert_eq!(value, restored);
+}
+
+fn parse(json: &str) -> JsValue {
+    js_sys::JSON::parse(json).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn unit() {
+    assert_eq!(to_value(&()).unwrap(), JsValue::UNDEFINED);
+    assert_eq!(to_value(&PhantomData::<u8>).unwrap(), JsValue::UNDEFINED);
+    from_value::<()>(JsValue::NULL).unwrap();
+    from_value::<()>(JsValue::UNDEFINED).unwrap();
+    from_value::<PhantomData<u8>>(JsValue::NULL).unwrap();
+    from_value::<()>(JsValue::from_f64(0.0)).unwrap_err();
+}
+
+#[wasm_bindgen_test]
+fn tuples() {
+    test((1_u8,), "[1]");
+    test((100_u32, "xyz".to_string(), true), r#"[100,"xyz",true]"#);
+
+    // std only implements `PartialEq` and `Debug` for tuples up to 12 elements.
+    let value = to_value(&(
+        1_u8, 2_u8, 3_u8, 4_u8, 5_u8, 6_u8, 7_u8, 8_u8, 9_u8, 10_u8, 11_u8, 12_u8, 13_u8, 14_u8,
+        15_u8, 16_u8,
+    ))
+    .unwrap();
+    assert_eq!(
+        js_sys::JSON::stringify(&value).unwrap(),
+        "[1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16]"
+    );
+    #[allow(clippy::type_complexity)]
+    let (a, .., p): (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8) =
+        from_value(value).unwrap();
+    assert_eq!((a, p), (1, 16));
+
+    from_value::<(u8, u8)>(parse("[1]")).unwrap_err();
+    from_value::<(u8, u8)>(parse("[1,2,3]")).unwrap_err();
+    from_value::<(u8, u8)>(parse(r#"{"0":1,"1":2}"#)).unwrap_err();
+}
+
+#[wasm_bindgen_test]
+fn sequences() {
+    test(Vec::<u8>::new(), "[]");
+    test(vec![1_u8, 2, 3], "[1,2,3]");
+    test(vec![vec![(1.5_f64, 2.5_f64)], vec![]], "[[[1.5,2.5]],[]]");
+
+    let slice: &[u8] = &[4, 5];
+    assert_eq!(
+        js_sys::JSON::stringify(&to_value(slice).unwrap()).unwrap(),
+        "[4,5]"
+    );
+}
+
+#[wasm_bindgen_test]
+fn unit_and_tuple_fields() {
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Status {
+        geo: (),
+        coordinates: Vec<(f64, f64)>,
+    }
+
+    test(
+        Status {
+            geo: (),
+            coordinates: vec![(1.5, 2.5)],
+        },
+        r#"{"coordinates":[[1.5,2.5]]}"#,
+    );
+    assert_eq!(
+        from_value::<Status>(parse(r#"{"geo":null,"coordinates":[]}"#)).unwrap(),
+        Status {
+            geo: (),
+            coordinates: vec![],
+        }
+    );
+}
+
+#[wasm_bindgen_test]
+fn wrappers() {
+    #[derive(Debug, SerJs, DeJs)]
+    struct State {
+        rc: Rc<String>,
+        arc: Arc<u8>,
+        cow: Cow<'static, str>,
+        cell: Cell<bool>,
+        ref_cell: RefCell<Vec<u8>>,
+        mutex: Mutex<u8>,
+        rw_lock: RwLock<u8>,
+    }
+
+    let state = State {
+        rc: Rc::new("rc".to_string()),
+        arc: Arc::new(1),
+        cow: Cow::Borrowed("cow"),
+        cell: Cell::new(true),
+        ref_cell: RefCell::new(vec![2]),
+        mutex: Mutex::new(3),
+        rw_lock: RwLock::new(4),
+    };
+    let json = r#"{"rc":"rc","arc":1,"cow":"cow","cell":true,"ref_cell":[2],"mutex":3,"rw_lock":4}"#;
+    let value = to_value(&state).unwrap();
+    assert_eq!(js_sys::JSON::stringify(&value).unwrap(), json);
+
+    let restored: State = from_value(value).unwrap();
+    assert_eq!(js_sys::JSON::stringify(&to_value(&restored).unwrap()).unwrap(), json);
+
+    let borrowed = &mut 5_u8;
+    assert_eq!(to_value(&borrowed).unwrap(), 5_u8);
+    assert_eq!(to_value(&&"str").unwrap(), "str");
+}
+
+#[wasm_bindgen_test]
+fn borrowed_ref_cell() {
+    let cell = RefCell::new(1_u8);
+    let _guard = cell.borrow_mut();
+    to_value(&cell).unwrap_err();
+    to_value(&vec![Some(&cell)]).unwrap_err();
+}
+
+#[wasm_bindgen_test]
+fn locked_mutex() {
+    let mutex = Mutex::new(1_u8);
+    let _guard = mutex.lock().unwrap();
+    to_value(&mutex).unwrap_err();
+
+    let rw_lock = RwLock::new(1_u8);
+    let _guard = rw_lock.write().unwrap();
+    to_value(&rw_lock).unwrap_err();
+}
+
+#[wasm_bindgen_test]
+fn shared_references() {
+    #[derive(Debug, SerJs, DeJs)]
+    struct Pair {
+        a: Rc<Vec<u8>>,
+        b: Rc<Vec<u8>>,
+    }
+
+    let shared = Rc::new(vec![1]);
+    let pair = Pair {
+        a: shared.clone(),
+        b: shared,
+    };
+
+    let copied = to_value(&pair).unwrap();
+    assert_ne!(
+        js_sys::Reflect::get(&copied, &"a".into()).unwrap(),
+        js_sys::Reflect::get(&copied, &"b".into()).unwrap()
+    );
+    let restored: Pair = from_value(copied).unwrap();
+    assert!(!Rc::ptr_eq(&restored.a, &restored.b));
+
+    let preserved = Serializer::new()
+        .preserve_references(true)
+        .serialize(&pair)
+        .unwrap();
+    assert_eq!(
+        js_sys::Reflect::get(&preserved, &"a".into()).unwrap(),
+        js_sys::Reflect::get(&preserved, &"b".into()).unwrap()
+    );
+    let restored: Pair = Deserializer::new()
+        .preserve_references(true)
+        .deserialize(preserved)
+        .unwrap();
+    assert!(Rc::ptr_eq(&restored.a, &restored.b));
+}
+
+#[wasm_bindgen_test]
+fn reference_cycles() {
+    #[derive(SerJs)]
+    struct Node {
+        next: Option<Rc<RefCell<Node>>>,
+    }
+
+    let node = Rc::new(RefCell::new(Node { next: None }));
+    node.borrow_mut().next = Some(node.clone());
+    to_value(&node).unwrap_err();
+    Serializer::new()
+        .preserve_references(true)
+        .serialize(&node)
+        .unwrap_err();
+    // Break the cycle so that the node can be dropped.
+    node.borrow_mut().next = None;
+}
+
+#[wasm_bindgen_test]
+fn results() {
+    test(Ok::<u8, String>(1), r#"{"Ok":1}"#);
+    test(Err::<u8, String>("failed".to_string()), r#"{"Err":"failed"}"#);
+    assert_eq!(
+        from_value::<Result<(), u8>>(parse(r#"{"Ok":null}"#)).unwrap(),
+        Ok(())
+    );
+    from_value::<Result<u8, u8>>(parse(r#"{"ok":true,"value":1}"#)).unwrap_err();
+
+    let serializer = Serializer::new().results_with_ok_flag(true);
+    let deserializer = Deserializer::new().results_with_ok_flag(true);
+    for (result, json) in [
+        (Ok(1), r#"{"ok":true,"value":1}"#),
+        (Err("failed".to_string()), r#"{"ok":false,"error":"failed"}"#),
+    ] {
+        let value = serializer.serialize(&result).unwrap();
+        assert_eq!(js_sys::JSON::stringify(&value).unwrap(), json);
+        let restored: Result<u8, String> = deserializer.deserialize(value).unwrap();
+        assert_eq!(restored, result);
+    }
+}
+
+#[wasm_bindgen_test]
+fn double_options() {
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Patch {
+        name: Option<Option<String>>,
+    }
+
+    test(Patch { name: None }, "{}");
+    test(Patch { name: Some(None) }, r#"{"name":null}"#);
+    test(
+        Patch {
+            name: Some(Some("name".to_string())),
+        },
+        r#"{"name":"name"}"#,
+    );
+    assert_eq!(
+        from_value::<Patch>(parse(r#"{"name":null}"#)).unwrap(),
+        Patch { name: Some(None) }
+    );
+}
+
+#[wasm_bindgen_test]
+fn none_as_null() {
+    let value = vec![Some(1_u8), None];
+    assert_eq!(
+        js_sys::JSON::stringify(&to_value(&value).unwrap()).unwrap(),
+        "[1,null]"
+    );
+    let js_value = Serializer::new().none_as_null(true).serialize(&value).unwrap();
+    assert!(Reflect::get_u32(&js_value, 1).unwrap().is_null());
+    assert_eq!(from_value::<Vec<Option<u8>>>(js_value).unwrap(), value);
+}
+
+#[wasm_bindgen_test]
+fn none_as() {
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    #[picoserde(none_as = "null")]
+    struct Fields {
+        null: Option<u8>,
+        #[picoserde(none_as = "undefined")]
+        undefined: Option<u8>,
+        #[picoserde(none_as = "omit")]
+        omit: Option<u8>,
+    }
+
+    let value = Fields {
+        null: None,
+        undefined: None,
+        omit: None,
+    };
+    let js_value = to_value(&value).unwrap();
+    assert_eq!(
+        js_sys::JSON::stringify(&js_value).unwrap(),
+        r#"{"null":null}"#
+    );
+    let keys = js_sys::Object::keys(js_value.unchecked_ref::<js_sys::Object>());
+    assert_eq!(keys.length(), 2);
+    assert!(keys.includes(&"undefined".into(), 0));
+    assert_eq!(from_value::<Fields>(js_value).unwrap(), value);
+
+    test(
+        Fields {
+            null: Some(1),
+            undefined: Some(2),
+            omit: Some(3),
+        },
+        r#"{"null":1,"undefined":2,"omit":3}"#,
+    );
+}
+
+#[wasm_bindgen_test]
+fn times() {
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Times {
+        created: SystemTime,
+        #[picoserde(proxy = "EpochMillis<SystemTime>")]
+        modified: SystemTime,
+        #[picoserde(proxy = "EpochMillis<SystemTime>")]
+        deleted: Option<SystemTime>,
+        timeout: Duration,
+    }
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Historic {
+        #[picoserde(proxy = "EpochMillis<JsDate>")]
+        at: JsDate,
+    }
+
+    let times = Times {
+        created: UNIX_EPOCH + Duration::from_millis(1_500),
+        modified: UNIX_EPOCH + Duration::from_millis(2_500),
+        deleted: None,
+        timeout: Duration::from_micros(2_500),
+    };
+    let value = to_value(&times).unwrap();
+    let created = Reflect::get(&value, &"created".into()).unwrap();
+    assert_eq!(created.unchecked_into::<js_sys::Date>().get_time(), 1_500.0);
+    assert_eq!(
+        js_sys::JSON::stringify(&value).unwrap(),
+        r#"{"created":"1970-01-01T00:00:01.500Z","modified":2500,"timeout":2.5}"#
+    );
+    assert_eq!(from_value::<Times>(value).unwrap(), times);
+
+    // `SystemTime` can't be before the epoch on wasm32-unknown-unknown, unlike
+    // `JsDate`.
+    test(
+        Historic {
+            at: JsDate::from_epoch_millis(-1_500.0).unwrap(),
+        },
+        r#"{"at":-1500}"#,
+    );
+    let before_epoch = js_sys::Date::new(&JsValue::from_f64(-1_500.0));
+    from_value::<SystemTime>(before_epoch.into()).unwrap_err();
+
+    let date = js_sys::Date::new(&JsValue::from_f64(f64::NAN));
+    from_value::<SystemTime>(date.into()).unwrap_err();
+    from_value::<JsDate>(JsValue::from_f64(0.0)).unwrap_err();
+    from_value::<Duration>(JsValue::from_f64(-1.0)).unwrap_err();
+    assert_eq!(
+        JsDate::from_epoch_millis(0.0).unwrap().to_system_time(),
+        Some(UNIX_EPOCH)
+    );
+}
+
+#[wasm_bindgen_test]
+fn error_paths() {
+    #[derive(Debug, DeJs)]
+    struct Item {
+        #[allow(dead_code)]
+        pair: (u8, (u8, u8)),
+    }
+
+    #[derive(Debug, DeJs)]
+    struct Order {
+        #[allow(dead_code)]
+        items: Vec<Item>,
+    }
+
+    let err = from_value::<Order>(parse(r#"{"items":[{"pair":[1,[2,3]]},{"pair":[1,[2]]}]}"#))
+        .unwrap_err();
+    assert_eq!(err.path(), "items[1].pair[1]");
+    match err.kind() {
+        DeJsErrKind::InvalidLength { len: 1, .. } => {}
+        kind => panic!("unexpected error kind {:?}", kind),
+    }
+
+    #[derive(Debug, DeJs)]
+    struct Counter {
+        #[allow(dead_code)]
+        count: u32,
+        #[allow(dead_code)]
+        flags: Vec<bool>,
+    }
+
+    let err = from_value::<Counter>(parse(r#"{"count":"x","flags":[]}"#)).unwrap_err();
+    assert_eq!(err.path(), "count");
+    assert!(matches!(err.kind(), DeJsErrKind::InvalidType(_)));
+    let err = from_value::<Counter>(parse(r#"{"count":1,"flags":[true,1]}"#)).unwrap_err();
+    assert_eq!(err.path(), "flags[1]");
+    from_value::<u8>(parse("256")).unwrap_err();
+    from_value::<u8>(parse("-1")).unwrap_err();
+    from_value::<i8>(parse("-129")).unwrap_err();
+    from_value::<String>(parse("1")).unwrap_err();
+}
+
+#[wasm_bindgen_test]
+fn sparse_arrays() {
+    // Only the actual items are allocated, not the 2 ** 32 - 1 of `length`.
+    let array = js_sys::Array::new();
+    array.set_length(u32::MAX);
+    let err = from_value::<Vec<u8>>(array.into()).unwrap_err();
+    assert_eq!(err.path(), "[0]");
+}
+
+#[wasm_bindgen_test]
+fn byte_arrays() {
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Message {
+        #[picoserde(proxy = "ByteArray<Vec<u8>>")]
+        payload: Vec<u8>,
+    }
+
+    let message = Message {
+        payload: vec![1, 2, 3],
+    };
+    let value = to_value(&message).unwrap();
+    let payload = Reflect::get(&value, &"payload".into()).unwrap();
+    assert_eq!(
+        payload.dyn_into::<js_sys::Uint8Array>().unwrap().to_vec(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(from_value::<Message>(value).unwrap(), message);
+    from_value::<Message>(parse(r#"{"payload":[1,2,3]}"#)).unwrap_err();
+}
+
+#[cfg(feature = "chrono")]
+#[wasm_bindgen_test]
+fn chrono() {
+    use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+    use picoserde_wasm_bindgen::Iso8601;
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Event {
+        start: DateTime<Utc>,
+        #[picoserde(proxy = "Iso8601<DateTime<Utc>>")]
+        end: DateTime<Utc>,
+        #[picoserde(proxy = "EpochMillis<DateTime<Utc>>")]
+        updated: DateTime<Utc>,
+        day: NaiveDate,
+    }
+
+    let date_time = Utc.with_ymd_and_hms(2020, 1, 2, 3, 4, 5).unwrap();
+    test(
+        Event {
+            start: date_time,
+            end: date_time,
+            updated: date_time,
+            day: NaiveDate::from_ymd_opt(2020, 1, 2).unwrap(),
+        },
+        r#"{"start":"2020-01-02T03:04:05.000Z","end":"2020-01-02T03:04:05Z","updated":1577934245000,"day":"2020-01-02"}"#,
+    );
+    from_value::<NaiveDate>("2020-13-01".into()).unwrap_err();
+    from_value::<Iso8601<DateTime<Utc>>>("yesterday".into()).unwrap_err();
+}
+
+#[cfg(feature = "time")]
+#[wasm_bindgen_test]
+fn time() {
+    use picoserde_wasm_bindgen::Iso8601;
+    use time::{Date, Month, OffsetDateTime};
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Event {
+        start: OffsetDateTime,
+        #[picoserde(proxy = "Iso8601<OffsetDateTime>")]
+        end: OffsetDateTime,
+        day: Date,
+    }
+
+    let date_time = OffsetDateTime::from_unix_timestamp(1_577_934_245).unwrap();
+    test(
+        Event {
+            start: date_time,
+            end: date_time,
+            day: Date::from_calendar_date(2020, Month::January, 2).unwrap(),
+        },
+        r#"{"start":"2020-01-02T03:04:05.000Z","end":"2020-01-02T03:04:05Z","day":"2020-01-02"}"#,
+    );
+    from_value::<Date>("2020-02-30".into()).unwrap_err();
+    from_value::<Date>("2020-1-2".into()).unwrap_err();
+    test(Date::MIN, r#""-9999-01-01""#);
+    test(Date::MAX, r#""9999-12-31""#);
+}
+
+#[cfg(all(feature = "uuid", feature = "url", feature = "semver"))]
+#[wasm_bindgen_test]
+fn identifiers() {
+    use semver::Version;
+    use url::Url;
+    use uuid::Uuid;
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Package {
+        id: Uuid,
+        #[picoserde(proxy = "ByteArray<Uuid>")]
+        raw_id: Uuid,
+        homepage: Url,
+        version: Version,
+    }
+
+    let id = Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+    let package = Package {
+        id,
+        raw_id: id,
+        homepage: Url::parse("https://example.com/").unwrap(),
+        version: Version::new(1, 2, 3),
+    };
+    let value = to_value(&package).unwrap();
+    assert_eq!(
+        Reflect::get(&value, &"id".into()).unwrap(),
+        "67e55044-10b1-426f-9247-bb680e5fe0c8"
+    );
+    assert_eq!(
+        Reflect::get(&value, &"raw_id".into())
+            .unwrap()
+            .dyn_into::<js_sys::Uint8Array>()
+            .unwrap()
+            .to_vec(),
+        id.as_bytes().to_vec()
+    );
+    assert_eq!(Reflect::get(&value, &"homepage".into()).unwrap(), "https://example.com/");
+    assert_eq!(Reflect::get(&value, &"version".into()).unwrap(), "1.2.3");
+    assert_eq!(from_value::<Package>(value).unwrap(), package);
+
+    let err = from_value::<Vec<Package>>(parse(
+        r#"[{"id":"nope","raw_id":null,"homepage":"https://example.com/","version":"1.2.3"}]"#,
+    ))
+    .unwrap_err();
+    assert_eq!(err.path(), "[0].id");
+}
+
+#[wasm_bindgen_test]
+fn custom_hashers() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashMap;
+    use std::hash::BuildHasherDefault;
+
+    let mut map = HashMap::<String, u8, BuildHasherDefault<DefaultHasher>>::default();
+    map.insert("a".to_string(), 1);
+    test(map, r#"{"a":1}"#);
+}
+
+#[cfg(feature = "indexmap")]
+#[wasm_bindgen_test]
+fn index_maps() {
+    use indexmap::{IndexMap, IndexSet};
+
+    let map: IndexMap<String, u8> = from_value(parse(r#"{"b":1,"a":2,"c":3}"#)).unwrap();
+    assert_eq!(map.keys().collect::<Vec<_>>(), ["b", "a", "c"]);
+    test(map, r#"{"b":1,"a":2,"c":3}"#);
+
+    let set: IndexSet<String> = from_value(parse(r#"["b","a","c"]"#)).unwrap();
+    test(set, r#"["b","a","c"]"#);
+}
+
+#[cfg(feature = "hashbrown")]
+#[wasm_bindgen_test]
+fn hashbrown_maps() {
+    let mut map = hashbrown::HashMap::new();
+    map.insert("a".to_string(), 1u8);
+    test(map, r#"{"a":1}"#);
+}
+
+#[cfg(all(feature = "smallvec", feature = "arrayvec", feature = "tinyvec"))]
+#[wasm_bindgen_test]
+fn inline_vecs() {
+    use smallvec::{smallvec, SmallVec};
+
+    let small: SmallVec<[u8; 2]> = smallvec![1, 2, 3];
+    test(small, "[1,2,3]");
+
+    let mut array = arrayvec::ArrayVec::<u8, 3>::new();
+    array.extend([1, 2, 3]);
+    test(array, "[1,2,3]");
+
+    let err = from_value::<arrayvec::ArrayVec<u8, 3>>(parse("[1,2,3,4]")).unwrap_err();
+    match err.kind() {
+        DeJsErrKind::InvalidLength { len: 4, .. } => {}
+        kind => panic!("unexpected error kind {:?}", kind),
+    }
+
+    test(tinyvec::array_vec!([u8; 3] => 1, 2), "[1,2]");
+    from_value::<tinyvec::ArrayVec<[u8; 1]>>(parse("[1,2]")).unwrap_err();
+    test(tinyvec::tiny_vec!([u8; 1] => 1, 2), "[1,2]");
+}
+
+#[cfg(all(feature = "rust_decimal", feature = "bigdecimal"))]
+#[wasm_bindgen_test]
+fn decimals() {
+    use std::str::FromStr;
+
+    use bigdecimal::BigDecimal;
+    use picoserde_wasm_bindgen::AsNumber;
+    use rust_decimal::Decimal;
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Payment {
+        amount: Decimal,
+        #[picoserde(proxy = "AsNumber<Decimal>")]
+        rate: Decimal,
+        total: BigDecimal,
+    }
+
+    test(
+        Payment {
+            amount: Decimal::from_str("12.30").unwrap(),
+            rate: Decimal::from_str("0.1").unwrap(),
+            total: BigDecimal::from_str("12345678901234567890.123456789").unwrap(),
+        },
+        r#"{"amount":"12.30","rate":0.1,"total":"12345678901234567890.123456789"}"#,
+    );
+
+    assert_eq!(from_value::<Decimal>(parse("1.5")).unwrap(), Decimal::new(15, 1));
+    assert_eq!(
+        from_value::<BigDecimal>(js_sys::BigInt::from(42u64).into()).unwrap(),
+        BigDecimal::from(42)
+    );
+    from_value::<Decimal>(parse("12345678901234567890")).unwrap_err();
+    from_value::<Decimal>(parse("1e-30")).unwrap_err();
+    from_value::<Decimal>(parse(r#""twelve""#)).unwrap_err();
+}
+
+#[cfg(feature = "bytes")]
+#[wasm_bindgen_test]
+fn bytes_buffers() {
+    use bytes::{Bytes, BytesMut};
+
+    let value = to_value(&Bytes::from_static(b"abc")).unwrap();
+    assert_eq!(
+        value.dyn_ref::<js_sys::Uint8Array>().unwrap().to_vec(),
+        b"abc"
+    );
+    assert_eq!(from_value::<Bytes>(value.clone()).unwrap(), &b"abc"[..]);
+    assert_eq!(from_value::<BytesMut>(value).unwrap(), &b"abc"[..]);
+    from_value::<Bytes>(parse("[1,2,3]")).unwrap_err();
+}
+
+#[wasm_bindgen_test]
+fn addresses_and_paths() {
+    use std::ffi::OsString;
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+    use std::path::PathBuf;
+
+    test(IpAddr::V4(Ipv4Addr::LOCALHOST), r#""127.0.0.1""#);
+    test(Ipv6Addr::LOCALHOST, r#""::1""#);
+    test(
+        SocketAddr::from((Ipv4Addr::new(10, 0, 0, 1), 8080)),
+        r#""10.0.0.1:8080""#,
+    );
+    from_value::<IpAddr>(parse(r#""300.0.0.1""#)).unwrap_err();
+
+    test(PathBuf::from("/tmp/file.txt"), r#""/tmp/file.txt""#);
+    test(OsString::from("name"), r#""name""#);
+    from_value::<PathBuf>(parse(r#""\ud800""#)).unwrap_err();
+}
+
+#[wasm_bindgen_test]
+fn raw_js_values() {
+    use js_sys::{Function, Object, Uint8Array};
+
+    #[derive(Debug, SerJs, DeJs)]
+    struct Handle {
+        config: JsValue,
+        options: Object,
+        callback: js_sys::Function,
+        data: Uint8Array,
+    }
+
+    let value = parse(r#"{"config":[1,{"a":true}],"options":{"b":2},"data":null}"#);
+    let callback = Function::new_no_args("return 42");
+    let data = Uint8Array::from(&[1u8, 2, 3][..]);
+    Reflect::set(&value, &"callback".into(), &callback).unwrap();
+    Reflect::set(&value, &"data".into(), &data).unwrap();
+
+    let handle = from_value::<Handle>(value.clone()).unwrap();
+    assert_eq!(handle.callback.call0(&JsValue::NULL).unwrap(), 42);
+    assert_eq!(handle.data.to_vec(), vec![1, 2, 3]);
+
+    let copy = to_value(&handle).unwrap();
+    for key in ["config", "options", "callback", "data"] {
+        assert_eq!(
+            Reflect::get(&copy, &key.into()).unwrap(),
+            Reflect::get(&value, &key.into()).unwrap()
+        );
+    }
+
+    Reflect::set(&value, &"callback".into(), &JsValue::TRUE).unwrap();
+    assert_eq!(from_value::<Handle>(value).unwrap_err().path(), "callback");
+}
+
+#[wasm_bindgen_test]
+fn dynamic_values() {
+    use picoserde_wasm_bindgen::Value;
+
+    let json = r#"{"b":null,"a":[true,1.5,"x",{}],"c":{"d":"e"}}"#;
+    let value: Value = from_value(parse(json)).unwrap();
+    assert_eq!(
+        value.as_object().unwrap().iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>(),
+        ["b", "a", "c"]
+    );
+    assert!(value["b"].is_null());
+    assert_eq!(value["a"][0].as_bool(), Some(true));
+    assert_eq!(value["a"][1].as_f64(), Some(1.5));
+    assert_eq!(value["a"][2].as_str(), Some("x"));
+    assert_eq!(value["c"]["d"], Value::from("e"));
+    assert!(value["missing"][3].is_undefined());
+    test(value, json);
+
+    let value: Value = from_value(js_sys::BigInt::from(7u64).into()).unwrap();
+    assert_eq!(value, Value::BigInt("7".to_string()));
+    assert!(to_value(&value).unwrap().is_bigint());
+
+    let bytes = js_sys::Uint8Array::from(&[1u8, 2][..]);
+    assert_eq!(from_value::<Value>(bytes.into()).unwrap(), Value::Bytes(vec![1, 2]));
+    from_value::<Value>(js_sys::Function::new_no_args("").into()).unwrap_err();
+
+    // Only plain objects, as the data of the others isn't in their properties.
+    let map: std::collections::HashMap<_, _> = vec![("a".to_string(), 1_u8)].into_iter().collect();
+    let no_prototype = to_value(&map).unwrap();
+    assert_eq!(from_value::<Value>(no_prototype).unwrap()["a"], Value::Number(1.0));
+    from_value::<Value>(js_sys::Date::new_0().into()).unwrap_err();
+    from_value::<Value>(js_sys::Map::new().into()).unwrap_err();
+    from_value::<Value>(js_sys::Set::new(&JsValue::UNDEFINED).into()).unwrap_err();
+    from_value::<Value>(js_sys::RegExp::new("a", "").into()).unwrap_err();
+    from_value::<Value>(js_sys::Float64Array::new_with_length(1).into()).unwrap_err();
+}
+
+#[wasm_bindgen_test]
+fn null_prototype_objects() {
+    use std::collections::HashMap;
+
+    let mut map = HashMap::new();
+    map.insert("__proto__".to_string(), 1u8);
+    map.insert("constructor".to_string(), 2u8);
+    let value = to_value(&map).unwrap();
+    assert!(js_sys::Object::get_prototype_of(&value).is_null());
+    assert_eq!(Reflect::get(&value, &"__proto__".into()).unwrap(), 1);
+    assert_eq!(Reflect::get(&value, &"constructor".into()).unwrap(), 2);
+    assert_eq!(from_value::<HashMap<String, u8>>(value).unwrap(), map);
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Point {
+        x: u8,
+    }
+
+    let value = to_value(&Point { x: 1 }).unwrap();
+    assert!(!js_sys::Object::get_prototype_of(&value).is_null());
+    let value = Serializer::new()
+        .null_prototype_structs(true)
+        .serialize(&Point { x: 1 })
+        .unwrap();
+    assert!(js_sys::Object::get_prototype_of(&value).is_null());
+    assert_eq!(from_value::<Point>(value).unwrap(), Point { x: 1 });
+}
+
+#[wasm_bindgen_test]
+fn untrusted_objects() {
+    use std::collections::HashMap;
+
+    use js_sys::{Function, Object};
+
+    #[derive(Debug, PartialEq, DeJs)]
+    struct Settings {
+        name: String,
+        admin: Option<bool>,
+    }
+
+    let untrusted = Deserializer::new().own_properties_only(true);
+    let prototype = Object::get_prototype_of(&Object::new());
+    Reflect::set(&prototype, &"admin".into(), &JsValue::TRUE).unwrap();
+    let polluted = from_value::<Settings>(parse(r#"{"name":"x"}"#));
+    let own = untrusted.deserialize::<Settings>(parse(r#"{"name":"x"}"#));
+    Reflect::delete_property(&prototype, &"admin".into()).unwrap();
+    assert_eq!(polluted.unwrap().admin, Some(true));
+    assert_eq!(own.unwrap().admin, None);
+
+    let value = parse(r#"{"admin":true}"#);
+    let getter = Object::new();
+    Reflect::set(&getter, &"get".into(), &Function::new_no_args("return 'x'")).unwrap();
+    Object::define_property(value.unchecked_ref::<Object>(), &"name".into(), &getter);
+    from_value::<Settings>(value.clone()).unwrap();
+    assert_eq!(untrusted.deserialize::<Settings>(value).unwrap_err().path(), "name");
+
+    let value = parse(r#"{"a":1,"__proto__":2}"#);
+    assert_eq!(from_value::<HashMap<String, u8>>(value.clone()).unwrap().len(), 2);
+    Deserializer::new()
+        .reject_prototype_keys(true)
+        .deserialize::<HashMap<String, u8>>(value.clone())
+        .unwrap_err();
+    untrusted.deserialize::<HashMap<String, u8>>(value.clone()).unwrap_err();
+    untrusted
+        .reject_prototype_keys(false)
+        .deserialize::<HashMap<String, u8>>(value)
+        .unwrap();
+
+    // Array holes don't read a polluted `Array.prototype`, and index getters
+    // aren't run.
+    let array_prototype = Object::get_prototype_of(&js_sys::Array::new());
+    Reflect::set(&array_prototype, &"1".into(), &JsValue::from(7)).unwrap();
+    let holes = parse("[1]");
+    Reflect::set(&holes, &"length".into(), &JsValue::from(2)).unwrap();
+    let polluted = from_value::<Vec<Option<u8>>>(holes.clone());
+    let own = untrusted.deserialize::<Vec<Option<u8>>>(holes.clone());
+    let own_tuple = untrusted.deserialize::<(u8, Option<u8>)>(holes);
+    Reflect::delete_property(&array_prototype, &"1".into()).unwrap();
+    assert_eq!(polluted.unwrap(), vec![Some(1), Some(7)]);
+    assert_eq!(own.unwrap(), vec![Some(1), None]);
+    assert_eq!(own_tuple.unwrap(), (1, None));
+
+    let value = parse("[1]");
+    Object::define_property(value.unchecked_ref::<Object>(), &"0".into(), &getter);
+    assert_eq!(untrusted.deserialize::<Vec<String>>(value).unwrap_err().path(), "[0]");
+}
+
+#[wasm_bindgen_test]
+fn throwing_getters_and_proxies() {
+    use js_sys::{Function, Object, Proxy};
+
+    #[derive(Debug, PartialEq, DeJs)]
+    struct Store {
+        name: String,
+        items: Vec<u8>,
+    }
+
+    let handler = Object::new();
+    let get = Function::new_with_args("target, key", "return target[key]");
+    Reflect::set(&handler, &"get".into(), &get).unwrap();
+    let proxy = |value: JsValue| JsValue::from(Proxy::new(&value, &handler));
+    let value = parse(r#"{"name":"x"}"#);
+    Reflect::set(&value, &"items".into(), &proxy(parse("[1,2]"))).unwrap();
+    assert_eq!(
+        from_value::<Store>(proxy(value)).unwrap(),
+        Store {
+            name: "x".to_string(),
+            items: vec![1, 2],
+        }
+    );
+
+    let value = parse(r#"{"items":[]}"#);
+    let getter = Object::new();
+    let throw = Function::new_no_args("throw new Error('boom')");
+    Reflect::set(&getter, &"get".into(), &throw).unwrap();
+    Object::define_property(value.unchecked_ref::<Object>(), &"name".into(), &getter);
+    let err = from_value::<Store>(value).unwrap_err();
+    assert_eq!(err.path(), "name");
+    let cause = match err.kind() {
+        DeJsErrKind::JsException(exception) => exception.clone(),
+        kind => panic!("unexpected error kind {:?}", kind),
+    };
+    let js_error = JsValue::from(err);
+    assert_eq!(Reflect::get(&js_error, &"cause".into()).unwrap(), cause);
+
+    let handler = Object::new();
+    let get = Function::new_with_args(
+        "target, key",
+        "if (key === 'length') return target.length; throw new Error('boom')",
+    );
+    Reflect::set(&handler, &"get".into(), &get).unwrap();
+    let value = JsValue::from(Proxy::new(&parse("[1]"), &handler));
+    assert_eq!(from_value::<Vec<u8>>(value).unwrap_err().path(), "[0]");
+}
+
+#[wasm_bindgen_test]
+fn js_classes() {
+    use js_sys::{Function, Object};
+    use picoserde_wasm_bindgen::register_js_class;
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    #[picoserde(js_class = "Point")]
+    struct Point {
+        x: f64,
+        y: f64,
+    }
+
+    #[derive(Debug, SerJs)]
+    #[picoserde(js_class = "Missing")]
+    struct Missing {
+        x: f64,
+    }
+
+    let class = Function::new_no_args(
+        "return class Point { norm() { return Math.hypot(this.x, this.y) } }",
+    )
+    .call0(&JsValue::NULL)
+    .unwrap();
+    register_js_class("Point", class.unchecked_ref());
+
+    let value = to_value(&Point { x: 3.0, y: 4.0 }).unwrap();
+    assert_eq!(
+        JsValue::from(Object::get_prototype_of(&value)),
+        Reflect::get(&class, &"prototype".into()).unwrap()
+    );
+    let norm = Reflect::get(&value, &"norm".into()).unwrap();
+    assert_eq!(norm.unchecked_into::<Function>().call0(&value).unwrap(), 5);
+    assert_eq!(from_value::<Point>(value).unwrap(), Point { x: 3.0, y: 4.0 });
+
+    to_value(&Missing { x: 1.0 }).unwrap_err();
+}
+
+#[wasm_bindgen_test]
+fn frozen_objects() {
+    use js_sys::{Object, Uint8Array};
+
+    #[derive(Debug, SerJs)]
+    struct Inner {
+        values: Vec<u8>,
+    }
+
+    #[derive(Debug, SerJs)]
+    struct Snapshot {
+        inner: Inner,
+        bytes: JsValue,
+        config: JsValue,
+    }
+
+    #[derive(Debug, SerJs)]
+    #[picoserde(freeze)]
+    struct Shallow {
+        inner: Inner,
+    }
+
+    #[derive(Debug, SerJs)]
+    #[picoserde(freeze = "deep")]
+    struct Deep {
+        inner: Inner,
+    }
+
+    let is_frozen = |value: &JsValue, path: &[&str]| {
+        let value = path.iter().fold(value.clone(), |value, key| {
+            Reflect::get(&value, &(*key).into()).unwrap()
+        });
+        Object::is_frozen(value.unchecked_ref::<Object>())
+    };
+    let inner = || Inner { values: vec![1] };
+    let snapshot = Snapshot {
+        inner: inner(),
+        bytes: Uint8Array::from(&[1u8][..]).into(),
+        config: parse(r#"{"nested":{}}"#),
+    };
+
+    let value = to_value(&snapshot).unwrap();
+    assert!(!is_frozen(&value, &[]));
+
+    let value = Serializer::new().freeze(true).serialize(&snapshot).unwrap();
+    assert!(is_frozen(&value, &[]));
+    assert!(!is_frozen(&value, &["inner"]));
+
+    let value = Serializer::new().deep_freeze(true).serialize(&snapshot).unwrap();
+    assert!(is_frozen(&value, &["inner", "values"]));
+    assert!(!is_frozen(&value, &["bytes"]));
+    // Objects passed through belong to the caller and are left alone.
+    assert!(!is_frozen(&value, &["config"]));
+    assert!(!is_frozen(&snapshot.config, &["nested"]));
+
+    let value = to_value(&Shallow { inner: inner() }).unwrap();
+    assert!(is_frozen(&value, &[]));
+    assert!(!is_frozen(&value, &["inner"]));
+
+    let value = to_value(&Deep { inner: inner() }).unwrap();
+    assert!(is_frozen(&value, &["inner", "values"]));
+
+    // Already frozen values still get their content frozen.
+    let value = Serializer::new()
+        .deep_freeze(true)
+        .serialize(&vec![Shallow { inner: inner() }])
+        .unwrap();
+    assert!(is_frozen(&value, &["0", "inner", "values"]));
+}
+
+#[wasm_bindgen_test]
+fn generics() {
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Page<T> {
+        items: Vec<T>,
+        total: u32,
+    }
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Labeled<'a, T: Clone + Debug, const N: usize, U = u8>
+    where
+        U: Copy,
+    {
+        label: Cow<'a, str>,
+        values: [T; N],
+        extra: Option<U>,
+    }
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Items<I: IntoIterator<Item = u8> = Vec<u8>> {
+        items: I,
+    }
+
+    // No `T: SerJs` bound for `PhantomData<T>`, nor for the proxied `T`.
+    struct Unit;
+
+    #[derive(Debug, SerJs, DeJs)]
+    struct Tagged<T> {
+        id: u32,
+        marker: PhantomData<T>,
+    }
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Stamped<T>
+    where
+        T: for<'a> From<&'a EpochMillis<T>>,
+        EpochMillis<T>: for<'a> From<&'a T>,
+    {
+        #[picoserde(proxy = "EpochMillis<T>")]
+        at: T,
+    }
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Shared<T> {
+        rc: Rc<T>,
+        arc: Option<Arc<Vec<T>>>,
+    }
+
+    #[derive(Debug, SerJs)]
+    struct Borrowed<'a, T> {
+        name: &'a str,
+        value: &'a T,
+        page: &'a Page<T>,
+    }
+
+    test(
+        Page {
+            items: vec!["a".to_string(), "b".to_string()],
+            total: 2,
+        },
+        r#"{"items":["a","b"],"total":2}"#,
+    );
+    test(
+        Labeled::<'static, u8, 2> {
+            label: Cow::Borrowed("x"),
+            values: [1, 2],
+            extra: Some(3),
+        },
+        r#"{"label":"x","values":[1,2],"extra":3}"#,
+    );
+    from_value::<Labeled<u8, 3>>(parse(r#"{"label":"x","values":[1,2]}"#)).unwrap_err();
+    test(Items { items: vec![1, 2] }, r#"{"items":[1,2]}"#);
+    test(
+        Shared {
+            rc: Rc::new(1_u8),
+            arc: Some(Arc::new(vec![2])),
+        },
+        r#"{"rc":1,"arc":[2]}"#,
+    );
+    let value = to_value(&Tagged::<Unit> {
+        id: 1,
+        marker: PhantomData,
+    })
+    .unwrap();
+    assert_eq!(from_value::<Tagged<Unit>>(value).unwrap().id, 1);
+    test(
+        Stamped {
+            at: UNIX_EPOCH + Duration::from_millis(1000),
+        },
+        r#"{"at":1000}"#,
+    );
+
+    let page = Page {
+        items: vec![true],
+        total: 1,
+    };
+    let value = to_value(&Borrowed {
+        name: "page",
+        value: &false,
+        page: &page,
+    })
+    .unwrap();
+    assert_eq!(
+        js_sys::JSON::stringify(&value).unwrap(),
+        r#"{"name":"page","value":false,"page":{"items":[true],"total":1}}"#
+    );
+}
+
+#[wasm_bindgen_test]
+fn tuple_structs() {
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Meters(f64);
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Point(i32, i32, Option<String>);
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Wrapper<T>(Vec<T>);
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Stamp(#[picoserde(proxy = "EpochMillis<SystemTime>")] SystemTime);
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Empty();
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct MaybeStamp(#[picoserde(proxy = "EpochMillis<SystemTime>")] Option<SystemTime>);
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    #[picoserde(none_as = "null")]
+    struct Range(Option<u8>, Option<u8>);
+
+    #[derive(Debug, SerJs)]
+    #[picoserde(freeze)]
+    struct Frozen(u8, u8);
+
+    test(Meters(1.5), "1.5");
+    test(Point(1, -2, Some("p".to_string())), r#"[1,-2,"p"]"#);
+    test(Wrapper(vec![true, false]), "[true,false]");
+    test(Stamp(UNIX_EPOCH + Duration::from_millis(1000)), "1000");
+
+    let err = from_value::<Point>(parse("[1,2]")).unwrap_err();
+    assert!(matches!(err.kind(), DeJsErrKind::InvalidLength { len: 2, .. }));
+    let err = from_value::<Point>(parse(r#"[1,"x",null]"#)).unwrap_err();
+    assert_eq!(err.path(), "[1]");
+    from_value::<Meters>(parse(r#""1.5""#)).unwrap_err();
+
+    test(Empty(), "[]");
+
+    test(MaybeStamp(Some(UNIX_EPOCH + Duration::from_millis(1000))), "1000");
+    assert!(to_value(&MaybeStamp(None)).unwrap().is_undefined());
+    test(Range(Some(1), None), "[1,null]");
+    let value = to_value(&Frozen(1, 2)).unwrap();
+    assert!(js_sys::Object::is_frozen(value.unchecked_ref::<js_sys::Object>()));
+    from_value::<Empty>(parse("[1]")).unwrap_err();
+}
+
+#[wasm_bindgen_test]
+fn unit_structs() {
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Marker;
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    #[picoserde(unit_as = "undefined")]
+    struct Nothing;
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    #[picoserde(accept_any)]
+    struct Ignored;
+
+    #[derive(Debug, PartialEq, SerJs, DeJs)]
+    struct Tagged {
+        marker: Marker,
+        nothing: Nothing,
+    }
+
+    test(Marker, "null");
+    assert!(to_value(&Nothing).unwrap().is_undefined());
+    assert_eq!(from_value::<Marker>(JsValue::UNDEFINED).unwrap(), Marker);
+    assert_eq!(from_value::<Nothing>(JsValue::NULL).unwrap(), Nothing);
+    let err = from_value::<Marker>(parse("{}")).unwrap_err();
+    assert!(matches!(err.kind(), DeJsErrKind::InvalidType(_)));
+    assert_eq!(from_value::<Ignored>(parse(r#"{"a":1}"#)).unwrap(), Ignored);
+    test(
+        Tagged {
+            marker: Marker,
+            nothing: Nothing,
+        },
+        r#"{"marker":null}"#,
+    );
+}